@@ -0,0 +1,108 @@
+//! Runtime key wrap algorithm identifiers
+#[cfg(feature = "alloc")]
+use crate::Error;
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+/// Key wrap algorithm supported by this crate.
+///
+/// Allows selecting the KW/KWP mode and the underlying block cipher at runtime,
+/// e.g. after parsing an algorithm identifier.
+///
+/// New algorithms may be added in minor releases, so `match` expressions
+/// on this enum outside of this crate must include a wildcard arm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum KeyWrapAlgorithm {
+    /// AES-128 key wrapping
+    KwAes128,
+    /// AES-192 key wrapping
+    KwAes192,
+    /// AES-256 key wrapping
+    KwAes256,
+    /// AES-128 key wrapping with padding
+    KwpAes128,
+    /// AES-192 key wrapping with padding
+    KwpAes192,
+    /// AES-256 key wrapping with padding
+    KwpAes256,
+}
+
+impl KeyWrapAlgorithm {
+    /// Returns `true` for the key wrap with padding (KWP) algorithms.
+    pub const fn is_padded(self) -> bool {
+        matches!(self, Self::KwpAes128 | Self::KwpAes192 | Self::KwpAes256)
+    }
+
+    /// Size of the key-encryption key in bytes.
+    pub const fn kek_len(self) -> usize {
+        match self {
+            Self::KwAes128 | Self::KwpAes128 => 16,
+            Self::KwAes192 | Self::KwpAes192 => 24,
+            Self::KwAes256 | Self::KwpAes256 => 32,
+        }
+    }
+}
+
+/// Run `$body` with `$ty` aliased to the wrapper type corresponding to `$alg`.
+#[cfg(feature = "alloc")]
+macro_rules! with_wrapper {
+    ($alg:expr, |$ty:ident| $body:block) => {
+        match $alg {
+            KeyWrapAlgorithm::KwAes128 => {
+                type $ty = crate::KwAes128;
+                $body
+            }
+            KeyWrapAlgorithm::KwAes192 => {
+                type $ty = crate::KwAes192;
+                $body
+            }
+            KeyWrapAlgorithm::KwAes256 => {
+                type $ty = crate::KwAes256;
+                $body
+            }
+            KeyWrapAlgorithm::KwpAes128 => {
+                type $ty = crate::KwpAes128;
+                $body
+            }
+            KeyWrapAlgorithm::KwpAes192 => {
+                type $ty = crate::KwpAes192;
+                $body
+            }
+            KeyWrapAlgorithm::KwpAes256 => {
+                type $ty = crate::KwpAes256;
+                $body
+            }
+        }
+    };
+}
+
+#[cfg(feature = "alloc")]
+impl KeyWrapAlgorithm {
+    /// Wrap `key` using key-encryption key `kek` and return wrapped key.
+    ///
+    /// Returns [`Error::InvalidKekSize`] if length of `kek` is not equal
+    /// to [`KeyWrapAlgorithm::kek_len`].
+    pub fn wrap(&self, kek: &[u8], key: &[u8]) -> Result<Vec<u8>, Error> {
+        with_wrapper!(self, |Wrapper| {
+            let kw = Wrapper::try_from(kek)?;
+            let mut buf = vec![0u8; Wrapper::required_wrap_len(key.len())?];
+            kw.wrap_key(key, &mut buf)?;
+            Ok(buf)
+        })
+    }
+
+    /// Unwrap `wkey` using key-encryption key `kek` and return unwrapped key.
+    ///
+    /// Returns [`Error::InvalidKekSize`] if length of `kek` is not equal
+    /// to [`KeyWrapAlgorithm::kek_len`].
+    pub fn unwrap(&self, kek: &[u8], wkey: &[u8]) -> Result<Vec<u8>, Error> {
+        with_wrapper!(self, |Wrapper| {
+            let kw = Wrapper::try_from(kek)?;
+            let mut buf = vec![0u8; Wrapper::required_unwrap_len(wkey.len())?];
+            let key_len = kw.unwrap_key(wkey, &mut buf)?.len();
+            buf.truncate(key_len);
+            Ok(buf)
+        })
+    }
+}
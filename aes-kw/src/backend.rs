@@ -0,0 +1,87 @@
+//! Key wrapping functions generic over block cipher backends
+use crate::{ctx::Ctx, Error, IV_LEN};
+use aes::cipher::{
+    typenum::U16, Block, BlockCipherDecBackend, BlockCipherDecClosure, BlockCipherEncBackend,
+    BlockCipherEncClosure,
+};
+
+/// Check that `buf` contains the integrity register and at least one semiblock.
+fn check_len(buf_len: usize) -> Result<(), Error> {
+    if buf_len % IV_LEN != 0 {
+        Err(Error::NotSemiblockAligned)
+    } else if buf_len < 2 * IV_LEN {
+        Err(Error::TooShort { min: 2 * IV_LEN })
+    } else {
+        Ok(())
+    }
+}
+
+/// Run the wrapping process W (RFC 3394 § 2.2.1) using block cipher `backend`.
+///
+/// The first [`IV_LEN`] bytes of `buf` are reserved for the integrity register
+/// and get overwritten with `C[0]`, while the rest of `buf` must contain
+/// the key semiblocks `P[1]..P[n]`, which get replaced by `C[1]..C[n]`.
+/// The integrity register is initialized with `iv`, e.g. [`AES_KW_IV`][crate::AES_KW_IV].
+///
+/// This function decouples the algorithm from the cipher ownership, e.g. it can be
+/// used with backends which offload block operations to an HSM or an accelerator.
+/// `backend` must implement a 128-bit block cipher and is called `6 * n` times
+/// with single blocks, one at a time. Parallel block methods are not used.
+///
+/// Returns [`Error::NotSemiblockAligned`] if length of `buf` is not a multiple
+/// of [`IV_LEN`] and [`Error::TooShort`] if `buf` does not contain at least
+/// one key semiblock.
+pub fn wrap_with_backend<B: BlockCipherEncBackend<BlockSize = U16>>(
+    backend: &B,
+    iv: &[u8; IV_LEN],
+    buf: &mut [u8],
+) -> Result<(), Error> {
+    check_len(buf.len())?;
+    let (a, body) = buf.split_at_mut(IV_LEN);
+
+    let block = &mut Block::<Ctx<'_>>::default();
+    block[..IV_LEN].copy_from_slice(iv);
+    let ctx = Ctx {
+        blocks_len: body.len() / IV_LEN,
+        block,
+        buf: body,
+    };
+    BlockCipherEncClosure::call(ctx, backend);
+
+    a.copy_from_slice(&block[..IV_LEN]);
+    Ok(())
+}
+
+/// Run the unwrapping process W^-1 (RFC 3394 § 2.2.2) using block cipher `backend`
+/// and check that the recovered integrity register is equal to `iv`.
+///
+/// `buf` must contain the wrapped key `C[0]..C[n]`. On success the key semiblocks
+/// `P[1]..P[n]` are stored in `buf` after the first [`IV_LEN`] bytes, which contain
+/// the recovered integrity register. On integrity check failure `buf` is zeroed.
+///
+/// The backend contract and length requirements are the same as in [`wrap_with_backend`].
+pub fn unwrap_with_backend<B: BlockCipherDecBackend<BlockSize = U16>>(
+    backend: &B,
+    iv: &[u8; IV_LEN],
+    buf: &mut [u8],
+) -> Result<(), Error> {
+    check_len(buf.len())?;
+    let (a, body) = buf.split_at_mut(IV_LEN);
+
+    let block = &mut Block::<Ctx<'_>>::default();
+    block[..IV_LEN].copy_from_slice(a);
+    let ctx = Ctx {
+        blocks_len: body.len() / IV_LEN,
+        block,
+        buf: body,
+    };
+    BlockCipherDecClosure::call(ctx, backend);
+
+    a.copy_from_slice(&block[..IV_LEN]);
+    if a == iv {
+        Ok(())
+    } else {
+        buf.fill(0);
+        Err(Error::IntegrityCheckFailed)
+    }
+}
@@ -0,0 +1,81 @@
+//! Length-prefixed framing for multiple wrapped keys
+use crate::Error;
+use alloc::vec::Vec;
+
+/// Size of the big-endian length prefix of a bundle entry in bytes.
+const LEN_PREFIX: usize = 4;
+
+/// Builder for a bundle of wrapped keys stored in a single buffer.
+///
+/// Every entry is encoded as a 4-byte big-endian length followed by the entry
+/// bytes. Encoded bundles can be split back into entries with [`KeyBundle::parse`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct KeyBundle(Vec<u8>);
+
+impl KeyBundle {
+    /// Create new empty bundle.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `wrapped` to the bundle.
+    ///
+    /// Returns [`Error::InvalidDataSize`] if length of `wrapped` does not fit into `u32`.
+    pub fn push(&mut self, wrapped: &[u8]) -> Result<(), Error> {
+        let len = u32::try_from(wrapped.len()).map_err(|_| Error::InvalidDataSize)?;
+        self.0.reserve(LEN_PREFIX + wrapped.len());
+        self.0.extend_from_slice(&len.to_be_bytes());
+        self.0.extend_from_slice(wrapped);
+        Ok(())
+    }
+
+    /// Get encoded bundle bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Convert into a vector of encoded bundle bytes.
+    #[inline]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// Split encoded bundle `bytes` into entries.
+    ///
+    /// Returned entries point into `bytes`. Returns [`Error::InvalidDataSize`]
+    /// if `bytes` is truncated in the middle of a length prefix or an entry.
+    pub fn parse(mut bytes: &[u8]) -> Result<Vec<&[u8]>, Error> {
+        let mut entries = Vec::new();
+        while !bytes.is_empty() {
+            if bytes.len() < LEN_PREFIX {
+                return Err(Error::InvalidDataSize);
+            }
+            let (len, rest) = bytes.split_at(LEN_PREFIX);
+            let len = u32::from_be_bytes(len.try_into().unwrap());
+            let len = usize::try_from(len).map_err(|_| Error::InvalidDataSize)?;
+            if rest.len() < len {
+                return Err(Error::InvalidDataSize);
+            }
+            let (entry, rest) = rest.split_at(len);
+            entries.push(entry);
+            bytes = rest;
+        }
+        Ok(entries)
+    }
+}
+
+impl AsRef<[u8]> for KeyBundle {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<KeyBundle> for Vec<u8> {
+    #[inline]
+    fn from(bundle: KeyBundle) -> Vec<u8> {
+        bundle.0
+    }
+}
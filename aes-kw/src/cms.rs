@@ -0,0 +1,54 @@
+//! Content-encryption key wrapping for CMS (RFC 5652 and RFC 5083)
+//!
+//! Helpers for the key-encryption step of the `KEKRecipientInfo` and
+//! `KeyAgreeRecipientInfo` structures, where the key wrap algorithm is
+//! identified by the `KeyEncryptionAlgorithmIdentifier` OID.
+use crate::{key_wrap_for_oid, Error, KeyWrapAlgorithm, IV_LEN};
+use alloc::vec::Vec;
+use const_oid::ObjectIdentifier;
+
+/// Minimum length of a content-encryption key wrapped with the KW algorithms.
+///
+/// RFC 3394 requires the key data to consist of at least two semiblocks.
+const KW_MIN_CEK_LEN: usize = 2 * IV_LEN;
+
+/// Find key wrap algorithm identified by `oid`.
+fn resolve(oid: &ObjectIdentifier) -> Result<KeyWrapAlgorithm, Error> {
+    key_wrap_for_oid(oid).ok_or(Error::UnsupportedAlgorithm)
+}
+
+/// Check that `cek_len` is supported by `alg`.
+fn check_cek_len(alg: KeyWrapAlgorithm, cek_len: usize) -> Result<(), Error> {
+    if !alg.is_padded() && cek_len < KW_MIN_CEK_LEN {
+        return Err(Error::InvalidDataSize);
+    }
+    Ok(())
+}
+
+/// Wrap content-encryption key `cek` with key-encryption key `kek` using
+/// the key wrap algorithm identified by `oid`.
+///
+/// Returns [`Error::UnsupportedAlgorithm`] if `oid` does not identify a supported
+/// key wrap algorithm and [`Error::InvalidDataSize`] if length of `cek` is
+/// not supported by the algorithm.
+pub fn wrap_cek(oid: &ObjectIdentifier, kek: &[u8], cek: &[u8]) -> Result<Vec<u8>, Error> {
+    let alg = resolve(oid)?;
+    check_cek_len(alg, cek.len())?;
+    alg.wrap(kek, cek)
+}
+
+/// Unwrap encrypted content-encryption key `wcek` with key-encryption key `kek`
+/// using the key wrap algorithm identified by `oid`.
+///
+/// Returns [`Error::UnsupportedAlgorithm`] if `oid` does not identify a supported
+/// key wrap algorithm and [`Error::InvalidDataSize`] if length of the unwrapped
+/// key is not supported by the algorithm.
+pub fn unwrap_cek(oid: &ObjectIdentifier, kek: &[u8], wcek: &[u8]) -> Result<Vec<u8>, Error> {
+    let alg = resolve(oid)?;
+    let mut cek = alg.unwrap(kek, wcek)?;
+    if let Err(err) = check_cek_len(alg, cek.len()) {
+        cek.fill(0);
+        return Err(err);
+    }
+    Ok(cek)
+}
@@ -1,38 +1,75 @@
 use crate::IV_LEN;
 use aes::cipher::{
-    typenum::U16, Block, BlockCipherDecBackend, BlockCipherDecClosure, BlockCipherEncBackend,
-    BlockCipherEncClosure, BlockSizeUser,
+    typenum::U16, Array, Block, BlockCipherDecBackend, BlockCipherDecClosure,
+    BlockCipherEncBackend, BlockCipherEncClosure, BlockSizeUser,
 };
 
+/// State of the `W` and `W^-1` functions.
+///
+/// `block` holds the integrity register `A` in its first semiblock, while `buf`
+/// holds `blocks_len` semiblocks `R[1]..R[n]`.
 pub(crate) struct Ctx<'a> {
     pub(crate) blocks_len: usize,
     pub(crate) block: &'a mut Block<Self>,
     pub(crate) buf: &'a mut [u8],
 }
 
+// The counter `t` never exceeds `6 * blocks_len`. Since `blocks_len` is at most
+// `usize::MAX / IV_LEN`, it always fits into `u64` on supported targets.
+const _: () = assert!((usize::MAX / IV_LEN) as u128 * 6 <= u64::MAX as u128);
+
+/// Compute the counter `t = n * j + i` for the 1-based semiblock index `i`.
+///
+/// The computation is performed using `u64` to prevent overflows of the intermediate
+/// product on targets where `usize` is narrower than `u64`.
+#[inline(always)]
+fn counter(blocks_len: usize, j: usize, i: usize) -> u64 {
+    blocks_len as u64 * j as u64 + i as u64
+}
+
+/// Split `block` into the integrity register `A` and the semiblock `R`.
+#[inline(always)]
+fn split_block(block: &mut Array<u8, U16>) -> (&mut [u8; IV_LEN], &mut [u8; IV_LEN]) {
+    let (a, r) = block.split_at_mut(IV_LEN);
+    (a.try_into().unwrap(), r.try_into().unwrap())
+}
+
+/// XOR big-endian representation of the counter `t` into `A`.
+#[inline(always)]
+fn xor_counter(a: &mut [u8; IV_LEN], t: u64) {
+    *a = (u64::from_be_bytes(*a) ^ t).to_be_bytes();
+}
+
 impl BlockSizeUser for Ctx<'_> {
     type BlockSize = U16;
 }
 
 /// Very similar to the W(S) function defined by NIST in SP 800-38F, Section 6.1
+///
+/// `buf` is split into exact semiblocks and the semiblocks are moved as
+/// fixed-size arrays, so the loop body does not contain any length checks.
 impl BlockCipherEncClosure for Ctx<'_> {
     #[inline(always)]
     fn call<B: BlockCipherEncBackend<BlockSize = U16>>(self, backend: &B) {
-        for j in 0..=5 {
-            for (i, chunk) in self.buf.chunks_mut(IV_LEN).skip(1).enumerate() {
+        debug_assert_eq!(self.buf.len(), self.blocks_len * IV_LEN);
+
+        // t = n * j + i, incremented for every processed semiblock
+        let mut t = 0u64;
+        for _ in 0..6 {
+            for chunk in self.buf.chunks_exact_mut(IV_LEN) {
+                let chunk: &mut [u8; IV_LEN] = chunk.try_into().unwrap();
+                t += 1;
+
                 // A | R[i]
-                self.block[IV_LEN..].copy_from_slice(chunk);
+                *split_block(self.block).1 = *chunk;
                 // B = AES(K, ..)
                 backend.encrypt_block(self.block.into());
 
+                let (a, r) = split_block(self.block);
                 // A = MSB(64, B) ^ t
-                let t = (self.blocks_len * j + (i + 1)) as u64;
-                for (ai, ti) in self.block[..IV_LEN].iter_mut().zip(&t.to_be_bytes()) {
-                    *ai ^= ti;
-                }
-
+                xor_counter(a, t);
                 // R[i] = LSB(64, B)
-                chunk.copy_from_slice(&self.block[IV_LEN..]);
+                *chunk = *r;
             }
         }
     }
@@ -42,16 +79,19 @@ impl BlockCipherEncClosure for Ctx<'_> {
 impl BlockCipherDecClosure for Ctx<'_> {
     #[inline(always)]
     fn call<B: BlockCipherDecBackend<BlockSize = U16>>(self, backend: &B) {
-        for j in (0..=5).rev() {
-            for (i, chunk) in self.buf.chunks_mut(IV_LEN).enumerate().rev() {
-                // A ^ t
-                let t = (self.blocks_len * j + (i + 1)) as u64;
-                for (ai, ti) in self.block[..IV_LEN].iter_mut().zip(&t.to_be_bytes()) {
-                    *ai ^= ti;
-                }
+        debug_assert_eq!(self.buf.len(), self.blocks_len * IV_LEN);
+
+        // t = n * j + i, decremented for every processed semiblock
+        let mut t = counter(self.blocks_len, 5, self.blocks_len);
+        for _ in 0..6 {
+            for chunk in self.buf.chunks_exact_mut(IV_LEN).rev() {
+                let chunk: &mut [u8; IV_LEN] = chunk.try_into().unwrap();
 
+                let (a, r) = split_block(self.block);
                 // (A ^ t) | R[i]
-                self.block[IV_LEN..].copy_from_slice(chunk);
+                xor_counter(a, t);
+                *r = *chunk;
+                t -= 1;
 
                 // B = AES-1(K, ..)
                 backend.decrypt_block(self.block.into());
@@ -60,8 +100,114 @@ impl BlockCipherDecClosure for Ctx<'_> {
                 // already set
 
                 // R[i] = LSB(64, B)
-                chunk.copy_from_slice(&self.block[IV_LEN..]);
+                *chunk = *split_block(self.block).1;
+            }
+        }
+    }
+}
+
+/// Batch of independent `W` computations performed within a single backend call.
+///
+/// Every key from `keys` is wrapped with the initial value `iv` into the corresponding
+/// buffer from `bufs`, reusing the same scratch block. Lengths of all keys and
+/// buffers must be validated beforehand.
+pub(crate) struct WrapBatchCtx<'a, 'b> {
+    pub(crate) iv: &'a [u8; IV_LEN],
+    pub(crate) keys: &'a [&'a [u8]],
+    pub(crate) bufs: &'a mut [&'b mut [u8]],
+}
+
+impl BlockSizeUser for WrapBatchCtx<'_, '_> {
+    type BlockSize = U16;
+}
+
+impl BlockCipherEncClosure for WrapBatchCtx<'_, '_> {
+    #[inline(always)]
+    fn call<B: BlockCipherEncBackend<BlockSize = U16>>(self, backend: &B) {
+        let block = &mut Block::<Ctx<'_>>::default();
+        for (key, buf) in self.keys.iter().zip(self.bufs.iter_mut()) {
+            let (a, body) = buf[..key.len() + IV_LEN].split_at_mut(IV_LEN);
+            body.copy_from_slice(key);
+            block[..IV_LEN].copy_from_slice(self.iv);
+
+            let ctx = Ctx {
+                blocks_len: key.len() / IV_LEN,
+                block: &mut *block,
+                buf: body,
+            };
+            BlockCipherEncClosure::call(ctx, backend);
+
+            a.copy_from_slice(&block[..IV_LEN]);
+        }
+    }
+}
+
+/// Batch of independent `W^-1` computations performed within a single backend call.
+///
+/// Every wrapped key from `wkeys` is unwrapped into the corresponding buffer
+/// from `bufs`, reusing the same scratch block. Processing stops at the first key
+/// for which the recovered integrity register is not equal to `iv`, its buffer
+/// gets zeroed and its index is written to `failed`. Lengths of all keys and
+/// buffers must be validated beforehand.
+pub(crate) struct UnwrapBatchCtx<'a, 'b> {
+    pub(crate) iv: &'a [u8; IV_LEN],
+    pub(crate) wkeys: &'a [&'a [u8]],
+    pub(crate) bufs: &'a mut [&'b mut [u8]],
+    pub(crate) failed: &'a mut Option<usize>,
+}
+
+impl BlockSizeUser for UnwrapBatchCtx<'_, '_> {
+    type BlockSize = U16;
+}
+
+impl BlockCipherDecClosure for UnwrapBatchCtx<'_, '_> {
+    #[inline(always)]
+    fn call<B: BlockCipherDecBackend<BlockSize = U16>>(self, backend: &B) {
+        let block = &mut Block::<Ctx<'_>>::default();
+        let expected_iv = u64::from_ne_bytes(*self.iv);
+        let iter = self.wkeys.iter().zip(self.bufs.iter_mut()).enumerate();
+        for (index, (wkey, buf)) in iter {
+            let (a, body) = wkey.split_at(IV_LEN);
+            let buf = &mut buf[..body.len()];
+            buf.copy_from_slice(body);
+            block[..IV_LEN].copy_from_slice(a);
+
+            let ctx = Ctx {
+                blocks_len: body.len() / IV_LEN,
+                block: &mut *block,
+                buf: &mut *buf,
+            };
+            BlockCipherDecClosure::call(ctx, backend);
+
+            let calc_iv = u64::from_ne_bytes(block[..IV_LEN].try_into().unwrap());
+            if calc_iv != expected_iv {
+                buf.fill(0);
+                *self.failed = Some(index);
+                return;
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{counter, IV_LEN};
+
+    #[test]
+    fn counter_progression() {
+        assert_eq!(counter(2, 0, 1), 1);
+        assert_eq!(counter(2, 0, 2), 2);
+        assert_eq!(counter(2, 1, 1), 3);
+        assert_eq!(counter(2, 5, 2), 12);
+
+        // Largest possible number of semiblocks
+        let n = usize::MAX / IV_LEN;
+        assert_eq!(counter(n, 0, n), n as u64);
+        assert_eq!(counter(n, 1, 1), n as u64 + 1);
+        assert_eq!(counter(n, 5, n), 6 * n as u64);
+
+        // Values which would overflow a 32-bit `usize`
+        let n = u32::MAX as usize / IV_LEN;
+        assert_eq!(counter(n, 5, n), 6 * n as u64);
+    }
+}
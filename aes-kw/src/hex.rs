@@ -0,0 +1,18 @@
+//! Hex formatting of wrapped keys
+use core::fmt;
+
+/// Wrapper which formats wrapped key bytes as uppercase hex.
+///
+/// It does not require `alloc`, so it can be used with [`write!`] on
+/// `no_std` targets, e.g. for debugging interoperability issues.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HexWrapped<'a>(pub &'a [u8]);
+
+impl fmt::Display for HexWrapped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for b in self.0 {
+            write!(f, "{b:02X}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+//! JWE algorithm identifiers from RFC 7518 § 4.4
+use crate::{KwAes128, KwAes192, KwAes256};
+
+/// JWE `alg` header parameter values for AES key wrap (RFC 7518 § 4.4).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum JweAlg {
+    /// AES key wrap with 128-bit key (`A128KW`)
+    A128Kw,
+    /// AES key wrap with 192-bit key (`A192KW`)
+    A192Kw,
+    /// AES key wrap with 256-bit key (`A256KW`)
+    A256Kw,
+}
+
+impl JweAlg {
+    /// Get the `alg` string identifier.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            JweAlg::A128Kw => "A128KW",
+            JweAlg::A192Kw => "A192KW",
+            JweAlg::A256Kw => "A256KW",
+        }
+    }
+
+    /// Parse an `alg` string identifier.
+    ///
+    /// Returns `None` for unknown algorithms.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(alg: &str) -> Option<Self> {
+        match alg {
+            "A128KW" => Some(JweAlg::A128Kw),
+            "A192KW" => Some(JweAlg::A192Kw),
+            "A256KW" => Some(JweAlg::A256Kw),
+            _ => None,
+        }
+    }
+
+    /// Size of the key-encryption key in bytes.
+    pub const fn key_len(self) -> usize {
+        match self {
+            JweAlg::A128Kw => 16,
+            JweAlg::A192Kw => 24,
+            JweAlg::A256Kw => 32,
+        }
+    }
+}
+
+impl core::fmt::Display for JweAlg {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl KwAes128 {
+    /// JWE `alg` identifier of this algorithm.
+    pub const JWE_ALG: JweAlg = JweAlg::A128Kw;
+}
+
+impl KwAes192 {
+    /// JWE `alg` identifier of this algorithm.
+    pub const JWE_ALG: JweAlg = JweAlg::A192Kw;
+}
+
+impl KwAes256 {
+    /// JWE `alg` identifier of this algorithm.
+    pub const JWE_ALG: JweAlg = JweAlg::A256Kw;
+}
@@ -1,13 +1,41 @@
-use core::ops::{Add, Rem};
+use core::{
+    fmt,
+    mem::MaybeUninit,
+    ops::{Add, Rem},
+};
 
-use crate::{ctx::Ctx, error::IntegrityCheckFailed, Error, IvLen, IV_LEN};
+use crate::{
+    ctx::{Ctx, UnwrapBatchCtx, WrapBatchCtx},
+    error::IntegrityCheckFailed,
+    traits::{fixed_iv, KeyWrapDecrypt, KeyWrapEncrypt},
+    BatchError, Error, IvLen, KwCipher, IV_LEN,
+};
 use aes::cipher::{
     array::ArraySize,
-    crypto_common::{InnerInit, InnerUser},
+    crypto_common::{BlockSizeUser, InnerInit, InnerUser},
     typenum::{Mod, NonZero, Sum, Zero, U16},
-    Array, Block, BlockCipherDecrypt, BlockCipherEncrypt,
+    Array, Block, BlockCipherDecrypt, BlockCipherEncrypt, Key, KeyInit, KeySizeUser,
 };
 
+#[cfg(feature = "zeroize")]
+use crate::SecretKey;
+#[cfg(feature = "base64")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "base64")]
+use base64ct::{Base64UrlUnpadded, Encoding};
+#[cfg(feature = "bytes")]
+use bytes::{Bytes, BytesMut};
+#[cfg(feature = "rand_core")]
+use rand_core::CryptoRngCore;
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+#[cfg(feature = "secrecy")]
+use secrecy::{ExposeSecret, SecretVec};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
 /// Default Initial Value for AES-KW as defined in RFC3394 § 2.2.3.1.
 ///
 /// <https://datatracker.ietf.org/doc/html/rfc3394#section-2.2.3.1>
@@ -24,52 +52,255 @@ use aes::cipher::{
 /// is corrupt is 2^-64.  If unwrapping produces A[0] any other value,
 /// then the unwrap must return an error and not return any key data.
 /// ```
-const IV: [u8; IV_LEN] = [0xA6, 0xA6, 0xA6, 0xA6, 0xA6, 0xA6, 0xA6, 0xA6];
+pub const AES_KW_IV: [u8; IV_LEN] = [0xA6, 0xA6, 0xA6, 0xA6, 0xA6, 0xA6, 0xA6, 0xA6];
 
 /// Type alias representing wrapped key roughly equivalent to `[u8; N + IV_LEN]`.
+///
+/// Return type of [`AesKw::wrap_fixed_key`], which can be used to store
+/// the wrapped key in a struct field:
+///
+/// ```
+/// use aes_kw::{cipher::consts::U32, KeyInit, KwAes256, KwWrappedKey};
+///
+/// struct StoredKey {
+///     wkey: KwWrappedKey<U32>,
+/// }
+///
+/// let kw = KwAes256::new(&[0x42; 32].into());
+/// let stored = StoredKey {
+///     wkey: kw.wrap_fixed_key::<U32>(&[0x24; 32].into()),
+/// };
+/// assert_eq!(stored.wkey.len(), 40);
+/// ```
 pub type KwWrappedKey<N> = Array<u8, Sum<N, IvLen>>;
 
 /// AES Key Wrapper (KW), as defined in [RFC 3394].
 ///
 /// [RFC 3394]: https://www.rfc-editor.org/rfc/rfc3394.txt
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct AesKw<C> {
+#[derive(Clone, Copy, PartialEq)]
+pub struct AesKw<C: KwCipher> {
     cipher: C,
 }
 
-impl<C> InnerUser for AesKw<C> {
+/// [`AesKw`] which borrows the inner block cipher.
+///
+/// It allows using a pre-initialized cipher (e.g. from a pool of KEKs) without
+/// moving or cloning it. All wrap and unwrap methods of [`AesKw`] are available,
+/// since the block cipher traits are implemented for references to ciphers.
+/// It can be created using [`AesKw::with_cipher_ref`].
+pub type AesKwRef<'a, C> = AesKw<&'a C>;
+
+impl<C: KwCipher> fmt::Debug for AesKw<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AesKw { ... }")
+    }
+}
+
+/// Check lengths of the key and output buffer for wrapping.
+///
+/// Returns length of the wrapped key.
+fn check_wrap_len(key_len: usize, buf_len: usize) -> Result<usize, Error> {
+    if key_len == 0 || key_len % IV_LEN != 0 {
+        return Err(Error::InvalidDataSize);
+    }
+
+    let expected_len = key_len.checked_add(IV_LEN).ok_or(Error::LengthOverflow)?;
+    if buf_len < expected_len {
+        return Err(Error::InvalidOutputSize { expected_len });
+    }
+    Ok(expected_len)
+}
+
+/// Check lengths of the wrapped key and output buffer for unwrapping.
+///
+/// Returns length of the unwrapped key.
+fn check_unwrap_len(wkey_len: usize, buf_len: usize) -> Result<usize, Error> {
+    let blocks_len = wkey_len / IV_LEN;
+    if wkey_len % IV_LEN != 0 {
+        return Err(Error::NotSemiblockAligned);
+    }
+    if blocks_len < 2 {
+        return Err(Error::TooShort { min: 2 * IV_LEN });
+    }
+
+    let expected_len = (blocks_len - 1) * IV_LEN;
+    if buf_len < expected_len {
+        return Err(Error::InvalidOutputSize { expected_len });
+    }
+    Ok(expected_len)
+}
+
+/// Check that keys of `key_len` bytes are supported and wrapped into `wkey_len` bytes.
+///
+/// Used in const contexts, so it fails compilation on unsupported lengths.
+const fn check_array_lens(key_len: usize, wkey_len: usize) {
+    assert!(
+        key_len != 0 && key_len % IV_LEN == 0,
+        "key length must be a positive multiple of IV_LEN"
+    );
+    assert!(
+        wkey_len == key_len + IV_LEN,
+        "wrapped key length must be equal to key length plus IV_LEN"
+    );
+}
+
+impl<C: KwCipher> InnerUser for AesKw<C> {
     type Inner = C;
 }
 
-impl<C> InnerInit for AesKw<C> {
+impl<C: KwCipher> BlockSizeUser for AesKw<C> {
+    type BlockSize = C::BlockSize;
+}
+
+impl<C: KwCipher> InnerInit for AesKw<C> {
     #[inline]
     fn inner_init(cipher: Self::Inner) -> Self {
         AesKw { cipher }
     }
 }
 
+impl<C: KwCipher> AesKw<C> {
+    /// Length of a semiblock in bytes, i.e. half of the cipher block.
+    pub const SEMIBLOCK_LEN: usize = IV_LEN;
+    /// Number of bytes added to a key by wrapping.
+    pub const OVERHEAD: usize = IV_LEN;
+    /// Minimum supported length of a key in bytes.
+    pub const MIN_KEY_LEN: usize = IV_LEN;
+    /// Maximum supported length of a key in bytes.
+    ///
+    /// Keys must also have length multiple of [`IV_LEN`].
+    ///
+    /// Unlike AES-KWP, AES-KW does not encode the key length, so the only limit is
+    /// the 64-bit counter `t`, which reaches `6 * n` for a key with `n` semiblocks.
+    /// This value never overflows since the number of semiblocks in a slice
+    /// is bounded by `usize::MAX / IV_LEN`.
+    pub const MAX_KEY_LEN: usize = (usize::MAX - IV_LEN) & !(IV_LEN - 1);
+
+    /// Get length of the wrapped key for a key of length `key_len`.
+    ///
+    /// Returns [`Error::InvalidDataSize`] if `key_len` is not supported and
+    /// [`Error::LengthOverflow`] if the wrapped length does not fit into `usize`.
+    #[inline]
+    pub fn required_wrap_len(key_len: usize) -> Result<usize, Error> {
+        check_wrap_len(key_len, usize::MAX)
+    }
+
+    /// Get length of the unwrapped key for a wrapped key of length `wkey_len`.
+    ///
+    /// Returns [`Error::NotSemiblockAligned`] or [`Error::TooShort`] if `wkey_len`
+    /// is not supported.
+    #[inline]
+    pub fn required_unwrap_len(wkey_len: usize) -> Result<usize, Error> {
+        check_unwrap_len(wkey_len, usize::MAX)
+    }
+
+    /// Get reference to the inner block cipher.
+    #[inline]
+    pub fn get_inner(&self) -> &C {
+        &self.cipher
+    }
+}
+
+impl<C: BlockSizeUser<BlockSize = U16>> AesKw<C> {
+    /// Create wrapper which borrows `cipher` instead of taking ownership of it.
+    #[inline]
+    pub fn with_cipher_ref(cipher: &C) -> AesKwRef<'_, C> {
+        AesKw { cipher }
+    }
+}
+
+impl<C: KwCipher + KeyInit> AesKw<C> {
+    /// Reinitialize the inner block cipher with a new key-encryption key.
+    ///
+    /// The old cipher state gets dropped in place. With the `zeroize` feature enabled
+    /// the block ciphers re-exported by this crate zeroize their key schedule on drop.
+    #[inline]
+    pub fn rekey(&mut self, key: &Key<C>) {
+        self.cipher = C::new(key);
+    }
+
+    /// Initialize wrapper with a random key-encryption key generated using `rng`.
+    ///
+    /// The temporary key buffer is zeroized after the cipher is initialized,
+    /// so the `rand_core` feature also enables `zeroize`.
+    #[cfg(feature = "rand_core")]
+    #[inline]
+    pub fn generate<R: CryptoRngCore + ?Sized>(rng: &mut R) -> Self {
+        let mut key = Key::<C>::default();
+        rng.fill_bytes(&mut key);
+        let res = Self::new(&key);
+        key.zeroize();
+        res
+    }
+
+    /// Initialize wrapper with a random key-encryption key generated using
+    /// the operating system's secure RNG.
+    ///
+    /// The temporary key buffer is zeroized after the cipher is initialized,
+    /// so the `getrandom` feature also enables `zeroize`.
+    #[cfg(feature = "getrandom")]
+    #[inline]
+    pub fn generate_kek() -> Result<Self, getrandom::Error> {
+        let mut key = Key::<C>::default();
+        let res = getrandom::getrandom(&mut key).map(|()| Self::new(&key));
+        key.zeroize();
+        res
+    }
+}
+
+impl<C: KwCipher> AsRef<C> for AesKw<C> {
+    #[inline]
+    fn as_ref(&self) -> &C {
+        &self.cipher
+    }
+}
+
+impl<C: KwCipher + KeyInit> TryFrom<&[u8]> for AesKw<C> {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: &[u8]) -> Result<Self, Error> {
+        C::new_from_slice(value)
+            .map(Self::inner_init)
+            .map_err(|_| Error::InvalidKekSize {
+                bits: value.len() * 8,
+            })
+    }
+}
+
 impl<C: BlockCipherEncrypt<BlockSize = U16>> AesKw<C> {
     /// Wrap key into `buf` assuming that it has correct length.
     fn wrap_key_trusted(&self, key: &[u8], buf: &mut [u8]) {
-        let blocks_len = key.len() / IV_LEN;
+        let (iv, body) = buf.split_at_mut(IV_LEN);
+        self.wrap_detached_trusted(key, iv.try_into().unwrap(), body);
+    }
+
+    /// Wrap key into `iv` and `body` assuming that `body` has correct length.
+    fn wrap_detached_trusted(&self, key: &[u8], iv: &mut [u8; IV_LEN], body: &mut [u8]) {
+        body.copy_from_slice(key);
+        self.wrap_in_place_trusted(iv, body);
+    }
+
+    /// Wrap key stored in `body` and write integrity register to `iv`.
+    fn wrap_in_place_trusted(&self, iv: &mut [u8; IV_LEN], body: &mut [u8]) {
+        let blocks_len = body.len() / IV_LEN;
 
         // 1) Initialize variables
 
         // Set A to the IV
         let block = &mut Block::<C>::default();
-        block[..IV_LEN].copy_from_slice(&IV);
+        block[..IV_LEN].copy_from_slice(&AES_KW_IV);
 
         // 2) Calculate intermediate values
-        buf[IV_LEN..].copy_from_slice(key);
 
         self.cipher.encrypt_with_backend(Ctx {
             blocks_len,
             block,
-            buf,
+            buf: body,
         });
 
         // 3) Output the results
-        buf[..IV_LEN].copy_from_slice(&block[..IV_LEN]);
+        iv.copy_from_slice(&block[..IV_LEN]);
     }
 
     /// Wrap `key` and write result to `buf`.
@@ -80,21 +311,316 @@ impl<C: BlockCipherEncrypt<BlockSize = U16>> AesKw<C> {
     /// Length of `buf` must be bigger or equal to `data.len() + IV_LEN`.
     #[inline]
     pub fn wrap_key<'a>(&self, key: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
-        let blocks_rem = key.len() % IV_LEN;
-        if blocks_rem != 0 {
-            return Err(Error::InvalidDataSize);
-        }
+        let n = self.wrap_key_into(key, buf)?;
+        Ok(&buf[..n])
+    }
 
-        let expected_len = key.len() + IV_LEN;
-        let buf = buf
+    /// Wrap key of cipher `C2` and write result to `buf`.
+    ///
+    /// Same as [`AesKw::wrap_key`], but the input is typed as a key of `C2`,
+    /// which is useful in key hierarchies. Key size of `C2` must be
+    /// a multiple of [`IV_LEN`].
+    #[inline]
+    pub fn wrap_cipher_key<'a, C2: KeySizeUser>(
+        &self,
+        key: &Key<C2>,
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        self.wrap_key(key, buf)
+    }
+
+    /// Wrap key stored in `buf` in place.
+    ///
+    /// The first [`IV_LEN`] bytes of `buf` are reserved for the integrity
+    /// register and get overwritten, while the rest of `buf` must contain the key.
+    /// Unlike [`AesKw::wrap_key`], the key is not copied into the output buffer,
+    /// so this method is preferable when the key can be written directly
+    /// after [`IV_LEN`] spare bytes. If the key has to be preserved,
+    /// use [`AesKw::wrap_key`] instead.
+    ///
+    /// Length of the key must be multiple of [`IV_LEN`] and bigger than zero.
+    #[inline]
+    pub fn wrap_key_in_place(&self, buf: &mut [u8]) -> Result<(), Error> {
+        let key_len = buf
+            .len()
+            .checked_sub(IV_LEN)
+            .ok_or(Error::InvalidDataSize)?;
+        check_wrap_len(key_len, buf.len())?;
+
+        let (iv, body) = buf.split_at_mut(IV_LEN);
+        self.wrap_in_place_trusted(iv.try_into().unwrap(), body);
+        Ok(())
+    }
+
+    /// Wrap `key` with `self` and then wrap the result with `outer`.
+    ///
+    /// This is useful in key hierarchies which wrap a key under two consecutive
+    /// KEKs. Both layers are computed directly in `buf` without any intermediate
+    /// buffers. The result can be unwrapped with [`AesKw::unwrap_key_twice`].
+    ///
+    /// Length of `key` must be multiple of [`IV_LEN`] and bigger than zero.
+    /// Length of `buf` must be bigger or equal to `key.len() + 2 * IV_LEN`.
+    pub fn wrap_key_twice<'a>(
+        &self,
+        outer: &AesKw<C>,
+        key: &[u8],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        let inner_len = check_wrap_len(key.len(), usize::MAX)?;
+        let outer_len = check_wrap_len(inner_len, buf.len())?;
+        let buf = &mut buf[..outer_len];
+
+        let (iv, body) = buf.split_at_mut(IV_LEN);
+        self.wrap_key_trusted(key, body);
+        outer.wrap_in_place_trusted(iv.try_into().unwrap(), body);
+        Ok(buf)
+    }
+
+    /// Wrap `key` and write result to `buf`.
+    ///
+    /// Same as [`AesKw::wrap_key`], but accepts any type which can be
+    /// referenced as a byte slice, e.g. `Vec<u8>` or `[u8; N]`.
+    #[inline]
+    pub fn wrap_key_ref<'a, K: AsRef<[u8]>>(
+        &self,
+        key: K,
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        self.wrap_key(key.as_ref(), buf)
+    }
+
+    /// Wrap `key` and write result to `buf`, then zeroize `key`.
+    ///
+    /// Same as [`AesKw::wrap_key`], but wipes the caller's plaintext buffer
+    /// after successful wrapping, so the secret does not linger at the call site.
+    /// Note that this method mutates the input. On error `key` is left untouched.
+    #[cfg(feature = "zeroize")]
+    #[inline]
+    pub fn wrap_key_consuming<'a>(
+        &self,
+        key: &mut [u8],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        let res = self.wrap_key(key, buf)?;
+        key.zeroize();
+        Ok(res)
+    }
+
+    /// Wrap `key` and write result to `buf`.
+    ///
+    /// Returns number of bytes written to `buf`.
+    ///
+    /// Length of `data` must be multiple of [`IV_LEN`] and bigger than zero.
+    /// Length of `buf` must be bigger or equal to `data.len() + IV_LEN`.
+    #[inline]
+    pub fn wrap_key_into(&self, key: &[u8], buf: &mut [u8]) -> Result<usize, Error> {
+        let expected_len = check_wrap_len(key.len(), buf.len())?;
+        self.wrap_key_trusted(key, &mut buf[..expected_len]);
+        Ok(expected_len)
+    }
+
+    /// Wrap `key` and append the wrapped key to `out`.
+    ///
+    /// `out` is extended by `key.len() + IV_LEN` bytes and the key is wrapped
+    /// directly in the new tail, which avoids a separate buffer when assembling
+    /// framed messages. On error `out` is left unchanged.
+    ///
+    /// Length requirements for `key` are the same as in [`AesKw::wrap_key`].
+    #[cfg(feature = "alloc")]
+    pub fn wrap_key_append(&self, key: &[u8], out: &mut Vec<u8>) -> Result<(), Error> {
+        let wkey_len = Self::required_wrap_len(key.len())?;
+        let start = out.len();
+        out.resize(start + wkey_len, 0);
+        self.wrap_key_trusted(key, &mut out[start..]);
+        Ok(())
+    }
+
+    /// Wrap `key` and return only the resulting integrity register `A[0]`.
+    ///
+    /// The key is wrapped into an internal buffer, which is discarded
+    /// afterwards. The returned value can be recorded e.g. in audit logs
+    /// as a checksum of the wrapped key.
+    ///
+    /// Note that the key can not be recovered from the returned value, so it
+    /// is not a substitute for storing the wrapped key. It also should not be
+    /// used as a MAC of `key` in other contexts.
+    ///
+    /// Length requirements for `key` are the same as in [`AesKw::wrap_key`].
+    #[cfg(feature = "alloc")]
+    pub fn integrity_tag(&self, key: &[u8]) -> Result<[u8; IV_LEN], Error> {
+        Self::required_wrap_len(key.len())?;
+        let mut body = vec![0u8; key.len()];
+        let mut tag = [0u8; IV_LEN];
+        self.wrap_detached_trusted(key, &mut tag, &mut body);
+        Ok(tag)
+    }
+
+    /// Wrap `key` and write the resulting integrity register `A[0]` to `iv_out`
+    /// and the wrapped semiblocks `C[1]..C[n]` to `body_out`.
+    ///
+    /// Concatenation of `iv_out` and `body_out[..key.len()]` is equal to
+    /// the output of [`AesKw::wrap_key`].
+    ///
+    /// Length of `key` must be multiple of [`IV_LEN`] and bigger than zero.
+    /// Length of `body_out` must be bigger or equal to `key.len()`.
+    #[inline]
+    pub fn wrap_key_detached(
+        &self,
+        key: &[u8],
+        iv_out: &mut [u8; IV_LEN],
+        body_out: &mut [u8],
+    ) -> Result<(), Error> {
+        check_wrap_len(key.len(), usize::MAX)?;
+        let expected_len = key.len();
+        let body_out = body_out
             .get_mut(..expected_len)
             .ok_or(Error::InvalidOutputSize { expected_len })?;
 
-        self.wrap_key_trusted(key, buf);
+        self.wrap_detached_trusted(key, iv_out, body_out);
+        Ok(())
+    }
+
+    /// Wrap `key` and return wrapped key stored in [`heapless::Vec`].
+    ///
+    /// Length of `key` must be multiple of [`IV_LEN`]. Returns
+    /// [`Error::InvalidOutputSize`] if `CAP` is smaller than `key.len() + IV_LEN`.
+    #[cfg(feature = "heapless")]
+    pub fn wrap_key_heapless<const CAP: usize>(
+        &self,
+        key: &[u8],
+    ) -> Result<heapless::Vec<u8, CAP>, Error> {
+        let expected_len = check_wrap_len(key.len(), CAP)?;
+        let mut buf = heapless::Vec::new();
+        buf.resize(expected_len, 0)
+            .map_err(|()| Error::InvalidOutputSize { expected_len })?;
+        self.wrap_key_trusted(key, &mut buf);
+        Ok(buf)
+    }
+
+    /// Wrap every key in `keys` and write results to the corresponding buffers in `bufs`.
+    ///
+    /// Lengths of all keys and buffers are validated before any wrapping takes place,
+    /// so on error none of the buffers get modified. A missing buffer is treated
+    /// as an empty one, while excess buffers are ignored.
+    ///
+    /// The returned [`BatchError`] contains index of the key which failed validation.
+    pub fn wrap_keys(&self, keys: &[&[u8]], bufs: &mut [&mut [u8]]) -> Result<(), BatchError> {
+        for (index, key) in keys.iter().enumerate() {
+            let buf_len = bufs.get(index).map_or(0, |buf| buf.len());
+            check_wrap_len(key.len(), buf_len).map_err(|error| BatchError { index, error })?;
+        }
+
+        self.cipher.encrypt_with_backend(WrapBatchCtx {
+            iv: &AES_KW_IV,
+            keys,
+            bufs,
+        });
 
+        Ok(())
+    }
+
+    /// Wrap `key` stored in [`SecretVec`] and return wrapped key.
+    ///
+    /// The key is exposed only for the duration of wrapping, which is performed
+    /// directly in the returned buffer, so no copies of the plaintext key are left.
+    ///
+    /// Length requirements for `key` are the same as in [`AesKw::wrap_key`].
+    #[cfg(feature = "secrecy")]
+    pub fn wrap_secret(&self, key: &SecretVec<u8>) -> Result<Vec<u8>, Error> {
+        let key = key.expose_secret();
+        let mut buf = vec![0u8; Self::required_wrap_len(key.len())?];
+        self.wrap_key_trusted(key, &mut buf);
         Ok(buf)
     }
 
+    /// Wrap every key in `keys` in parallel and return wrapped keys
+    /// in the same order.
+    ///
+    /// Keys are wrapped independently using the [`rayon`] global thread pool,
+    /// which is useful for re-wrapping a big set of keys under a new KEK.
+    /// If any key has unsupported length, an error is returned
+    /// and no wrapped keys are produced.
+    #[cfg(feature = "rayon")]
+    pub fn wrap_keys_par<K: AsRef<[u8]> + Sync>(&self, keys: &[K]) -> Result<Vec<Vec<u8>>, Error>
+    where
+        C: Sync,
+    {
+        keys.par_iter()
+            .map(|key| {
+                let key = key.as_ref();
+                let mut buf = vec![0u8; Self::required_wrap_len(key.len())?];
+                self.wrap_key_trusted(key, &mut buf);
+                Ok(buf)
+            })
+            .collect()
+    }
+
+    /// Apply the wrapping function `W` defined in NIST SP 800-38F § 6.1 to `data`
+    /// using `a` as the initial value of the integrity register `A`.
+    ///
+    /// On input `a || data` forms the input string `S`, with `data` containing
+    /// semiblocks `R[1]..R[n]`. On output `a || data` contains `W(S)`, i.e. `a`
+    /// holds `C[0]` and `data` holds `C[1]..C[n]`. Unlike [`AesKw::wrap_key`],
+    /// no default IV is imposed.
+    ///
+    /// Length of `data` must be multiple of [`IV_LEN`] and bigger than zero.
+    /// SP 800-38F defines `W` only for 2 or more semiblocks, but a single semiblock
+    /// is accepted as well for consistency with [`AesKw::wrap_key`], which supports
+    /// 8-byte keys. Callers which need strict SP 800-38F conformance must check
+    /// that `data` is at least `2 * IV_LEN` bytes long.
+    #[inline]
+    pub fn wrap_semiblocks(&self, a: &mut [u8; IV_LEN], data: &mut [u8]) -> Result<(), Error> {
+        if data.is_empty() || data.len() % IV_LEN != 0 {
+            return Err(Error::InvalidDataSize);
+        }
+
+        let block = &mut Block::<C>::default();
+        block[..IV_LEN].copy_from_slice(a);
+
+        self.cipher.encrypt_with_backend(Ctx {
+            blocks_len: data.len() / IV_LEN,
+            block,
+            buf: data,
+        });
+
+        a.copy_from_slice(&block[..IV_LEN]);
+        Ok(())
+    }
+
+    /// Wrap `key` using OpenPGP ECDH padding and write result to `buf`.
+    ///
+    /// As defined in RFC 6637 § 8, `key` is padded to a multiple of [`IV_LEN`]
+    /// using the PKCS#5 method (i.e. with `k` bytes of value `k`, where `k` is
+    /// between 1 and [`IV_LEN`]) and then wrapped as in [`AesKw::wrap_key`].
+    /// Note that this padding is different from the one used by AES-KWP.
+    ///
+    /// Returns slice which points to `buf` and contains wrapped data.
+    ///
+    /// Length of `buf` must be bigger or equal to
+    /// `(key.len() / IV_LEN + 1) * IV_LEN + IV_LEN`.
+    #[inline]
+    pub fn wrap_pgp<'a>(&self, key: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        let pad_len = IV_LEN - key.len() % IV_LEN;
+        let expected_len = key
+            .len()
+            .checked_add(pad_len + IV_LEN)
+            .ok_or(Error::LengthOverflow)?;
+        if buf.len() < expected_len {
+            return Err(Error::InvalidOutputSize { expected_len });
+        }
+
+        let (iv, body) = buf[..expected_len].split_at_mut(IV_LEN);
+        let (body_key, body_pad) = body.split_at_mut(key.len());
+        body_key.copy_from_slice(key);
+        body_pad.fill(pad_len as u8);
+
+        let iv: &mut [u8; IV_LEN] = iv.try_into().unwrap();
+        *iv = AES_KW_IV;
+        self.wrap_semiblocks(iv, body)?;
+
+        Ok(&buf[..expected_len])
+    }
+
     /// Wrap fixed-size key `key` and return wrapped key.
     ///
     /// This method is roughly equivalent to:
@@ -114,7 +640,10 @@ impl<C: BlockCipherEncrypt<BlockSize = U16>> AesKw<C> {
     /// ```
     /// but uses [`hybrid_array::Array`][Array] instead of built-in arrays
     /// to work around current limitations of the const generics system.
+    /// Built-in arrays can be converted to and from [`Array`] using
+    /// the [`From`]/[`Into`] traits.
     #[inline]
+    #[must_use]
     pub fn wrap_fixed_key<N>(&self, key: &Array<u8, N>) -> KwWrappedKey<N>
     where
         N: ArraySize + NonZero + Add<IvLen> + Rem<IvLen>,
@@ -125,6 +654,54 @@ impl<C: BlockCipherEncrypt<BlockSize = U16>> AesKw<C> {
         self.wrap_key_trusted(key, &mut buf);
         buf
     }
+
+    /// Wrap `key` and return wrapped key stored in a built-in array.
+    ///
+    /// Same as [`AesKw::wrap_fixed_key`], but uses built-in arrays instead of [`Array`].
+    /// Since `N + IV_LEN` can not be used as an array length on stable Rust yet,
+    /// length of the wrapped key is passed as a separate parameter `M`, which usually
+    /// gets inferred from the context. Both `N` being a positive multiple of [`IV_LEN`]
+    /// and `M` being equal to `N + IV_LEN` are checked at compile time.
+    ///
+    /// ```
+    /// use aes_kw::{KeyInit, KwAes256};
+    ///
+    /// let kw = KwAes256::new(&[0x42; 32].into());
+    /// let wkey: [u8; 40] = kw.wrap_array(&[0x24; 32]);
+    /// let key: [u8; 32] = kw.unwrap_array(&wkey).unwrap();
+    /// assert_eq!(key, [0x24; 32]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn wrap_array<const N: usize, const M: usize>(&self, key: &[u8; N]) -> [u8; M] {
+        const { check_array_lens(N, M) };
+        let mut buf = [0u8; M];
+        self.wrap_key_trusted(key, &mut buf);
+        buf
+    }
+
+    /// Wrap `key` and return wrapped key encoded as unpadded base64url string.
+    ///
+    /// Length of `key` must be multiple of [`IV_LEN`].
+    #[cfg(feature = "base64")]
+    pub fn wrap_key_b64(&self, key: &[u8]) -> Result<String, Error> {
+        let mut buf = vec![0u8; key.len() + IV_LEN];
+        let wkey = self.wrap_key(key, &mut buf)?;
+        Ok(Base64UrlUnpadded::encode_string(wkey))
+    }
+
+    /// Wrap `key` and return wrapped key as [`Bytes`].
+    ///
+    /// The key is wrapped directly into a [`BytesMut`] buffer of the exact size,
+    /// which gets frozen afterwards.
+    ///
+    /// Length requirements for `key` are the same as in [`AesKw::wrap_key`].
+    #[cfg(feature = "bytes")]
+    pub fn wrap_key_bytes(&self, key: &[u8]) -> Result<Bytes, Error> {
+        let mut buf = BytesMut::zeroed(Self::required_wrap_len(key.len())?);
+        self.wrap_key_trusted(key, &mut buf);
+        Ok(buf.freeze())
+    }
 }
 
 impl<C: BlockCipherDecrypt<BlockSize = U16>> AesKw<C> {
@@ -133,16 +710,27 @@ impl<C: BlockCipherDecrypt<BlockSize = U16>> AesKw<C> {
         &self,
         wkey: &[u8],
         buf: &'a mut [u8],
+    ) -> Result<&'a [u8], IntegrityCheckFailed> {
+        let (iv, body) = wkey.split_at(IV_LEN);
+        self.unwrap_detached_trusted(iv, body, buf)
+    }
+
+    /// Unwrap key in `iv` and `body` into `buf` assuming that it has correct length.
+    fn unwrap_detached_trusted<'a>(
+        &self,
+        iv: &[u8],
+        body: &[u8],
+        buf: &'a mut [u8],
     ) -> Result<&'a [u8], IntegrityCheckFailed> {
         let blocks_len = buf.len() / IV_LEN;
 
         // 1) Initialize variables
 
         let block = &mut Block::<C>::default();
-        block[..IV_LEN].copy_from_slice(&wkey[..IV_LEN]);
+        block[..IV_LEN].copy_from_slice(iv);
 
         //   for i = 1 to n: R[i] = C[i]
-        buf.copy_from_slice(&wkey[IV_LEN..]);
+        buf.copy_from_slice(body);
 
         // 2) Calculate intermediate values
 
@@ -154,7 +742,7 @@ impl<C: BlockCipherDecrypt<BlockSize = U16>> AesKw<C> {
 
         // 3) Output the results
 
-        let expected_iv = u64::from_ne_bytes(IV);
+        let expected_iv = u64::from_ne_bytes(AES_KW_IV);
         let calc_iv = u64::from_ne_bytes(block[..IV_LEN].try_into().unwrap());
         if calc_iv == expected_iv {
             Ok(buf)
@@ -168,21 +756,12 @@ impl<C: BlockCipherDecrypt<BlockSize = U16>> AesKw<C> {
     ///
     /// Returns slice which points to `buf` and contains unwrapped data.
     ///
-    /// Length of `data` must be multiple of [`IV_LEN`] and bigger than zero.
-    /// Length of `buf` must be bigger or equal to `data.len()`.
+    /// Length of `data` must be multiple of [`IV_LEN`] and bigger than [`IV_LEN`].
+    /// Length of `buf` must be bigger or equal to `data.len() - IV_LEN`.
     #[inline]
     pub fn unwrap_key<'a>(&self, wkey: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
-        let blocks_len = wkey.len() / IV_LEN;
-        let blocks_rem = wkey.len() % IV_LEN;
-        if blocks_rem != 0 || blocks_len < 1 {
-            return Err(Error::InvalidDataSize);
-        }
-
-        let blocks_len = blocks_len - 1;
-        let expected_len = blocks_len * IV_LEN;
-        let buf = buf
-            .get_mut(..expected_len)
-            .ok_or(Error::InvalidOutputSize { expected_len })?;
+        let expected_len = check_unwrap_len(wkey.len(), buf.len())?;
+        let buf = &mut buf[..expected_len];
 
         self.unwrap_key_trusted(wkey, buf)
             .map_err(|_| Error::IntegrityCheckFailed)?;
@@ -190,6 +769,265 @@ impl<C: BlockCipherDecrypt<BlockSize = U16>> AesKw<C> {
         Ok(buf)
     }
 
+    /// Unwrap `wkey` produced by [`AesKw::wrap_key_twice`], removing the layer of
+    /// `outer` first and then the layer of `self`.
+    ///
+    /// Integrity of both layers is verified. Since the outer layer has to be unwrapped
+    /// before the inner one, `buf` is also used for the intermediate wrapped key and
+    /// its length must be bigger or equal to `wkey.len() - IV_LEN`. The returned slice
+    /// points into `buf` and contains `wkey.len() - 2 * IV_LEN` bytes.
+    ///
+    /// On integrity check failure of either layer `buf` is zeroed.
+    pub fn unwrap_key_twice<'a>(
+        &self,
+        outer: &AesKw<C>,
+        wkey: &[u8],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        let inner_len = check_unwrap_len(wkey.len(), buf.len())?;
+        if inner_len < 2 * IV_LEN {
+            return Err(Error::TooShort { min: 3 * IV_LEN });
+        }
+        let buf = &mut buf[..inner_len];
+
+        outer.unwrap_key_trusted(wkey, buf)?;
+
+        let (iv, body) = buf.split_at_mut(IV_LEN);
+        self.unwrap_in_place_trusted(iv, body)?;
+        Ok(body)
+    }
+
+    /// Unwrap `wkey` and write result to `buf`, reporting the recovered
+    /// integrity register on failure.
+    ///
+    /// Same as [`AesKw::unwrap_key`], but on integrity check failure returns
+    /// [`Error::IntegrityCheckFailedDebug`] with the computed `A[0]`, which helps
+    /// to diagnose e.g. a wrong KEK or a non-default IV.
+    ///
+    /// This method is intended only for debugging, see the security note
+    /// on [`Error::IntegrityCheckFailedDebug`].
+    #[cfg(feature = "debug-errors")]
+    pub fn unwrap_key_debug<'a>(&self, wkey: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        let expected_len = check_unwrap_len(wkey.len(), buf.len())?;
+        let buf = &mut buf[..expected_len];
+
+        let (a, body) = wkey.split_at(IV_LEN);
+        let mut a: [u8; IV_LEN] = a.try_into().unwrap();
+        buf.copy_from_slice(body);
+        self.unwrap_semiblocks(&mut a, buf)?;
+
+        if a == AES_KW_IV {
+            Ok(buf)
+        } else {
+            buf.fill(0);
+            Err(Error::IntegrityCheckFailedDebug { computed: a })
+        }
+    }
+
+    /// Unwrap `data` and write result to `buf`.
+    ///
+    /// Same as [`AesKw::unwrap_key`], but length of `buf` must be exactly equal
+    /// to `data.len() - IV_LEN`, otherwise [`Error::InvalidOutputSize`] is returned.
+    #[inline]
+    pub fn unwrap_key_exact<'a>(&self, wkey: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        let expected_len = check_unwrap_len(wkey.len(), buf.len())?;
+        if buf.len() != expected_len {
+            return Err(Error::InvalidOutputSize { expected_len });
+        }
+        self.unwrap_key(wkey, buf)
+    }
+
+    /// Unwrap `wkey` into `scratch` and pass the unwrapped key to `f`.
+    ///
+    /// `scratch` is zeroed after `f` returns, so the unwrapped key does not outlive
+    /// the call. `f` is called only if the integrity check passes.
+    ///
+    /// Size requirements for `wkey` and `scratch` are the same as in [`AesKw::unwrap_key`].
+    #[inline]
+    pub fn unwrap_key_with<R, F: FnOnce(&[u8]) -> R>(
+        &self,
+        wkey: &[u8],
+        scratch: &mut [u8],
+        f: F,
+    ) -> Result<R, Error> {
+        let res = self.unwrap_key(wkey, scratch).map(f);
+        scratch.fill(0);
+        res
+    }
+
+    /// Unwrap `wkey` and return unwrapped key stored in [`SecretVec`].
+    ///
+    /// The key is unwrapped directly into the returned buffer, which gets zeroed
+    /// on integrity check failure.
+    ///
+    /// Length requirements for `wkey` are the same as in [`AesKw::unwrap_key`].
+    #[cfg(feature = "secrecy")]
+    pub fn unwrap_secret(&self, wkey: &[u8]) -> Result<SecretVec<u8>, Error> {
+        let mut buf = vec![0u8; Self::required_unwrap_len(wkey.len())?];
+        self.unwrap_key_trusted(wkey, &mut buf)?;
+        Ok(SecretVec::new(buf))
+    }
+
+    /// Check integrity of `wkey` without exposing the unwrapped key.
+    ///
+    /// The key is unwrapped into an internal buffer, which gets zeroed
+    /// before returning. Returns [`Error::IntegrityCheckFailed`] if `wkey`
+    /// was not wrapped using this KEK.
+    ///
+    /// Length requirements for `wkey` are the same as in [`AesKw::unwrap_key`].
+    #[cfg(feature = "alloc")]
+    pub fn verify(&self, wkey: &[u8]) -> Result<(), Error> {
+        let mut scratch = vec![0u8; Self::required_unwrap_len(wkey.len())?];
+        self.unwrap_key_with(wkey, &mut scratch, |_| ())
+    }
+
+    /// Unwrap `wkey` and initialize cipher `C2` using the unwrapped key.
+    ///
+    /// The unwrapped key is stored in a temporary buffer which gets zeroed
+    /// after the cipher is initialized.
+    ///
+    /// Returns [`Error::InvalidDataSize`] if length of the unwrapped key is not
+    /// equal to key size of `C2`.
+    #[inline]
+    pub fn unwrap_cipher<C2: KeyInit>(&self, wkey: &[u8]) -> Result<C2, Error> {
+        let mut key = self.unwrap_cipher_key::<C2>(wkey)?;
+        let cipher = C2::new(&key);
+        key.fill(0);
+        Ok(cipher)
+    }
+
+    /// Unwrap `wkey` and return it as a key of cipher `C2`.
+    ///
+    /// This is the inverse of [`AesKw::wrap_cipher_key`]. Returns
+    /// [`Error::InvalidDataSize`] if length of the unwrapped key is not
+    /// equal to key size of `C2`.
+    #[inline]
+    pub fn unwrap_cipher_key<C2: KeySizeUser>(&self, wkey: &[u8]) -> Result<Key<C2>, Error> {
+        if wkey.len() != C2::key_size() + IV_LEN {
+            return Err(Error::InvalidDataSize);
+        }
+
+        let mut key = Key::<C2>::default();
+        self.unwrap_key(wkey, &mut key)?;
+        Ok(key)
+    }
+
+    /// Unwrap key stored as a separate integrity register `iv` (`C[0]`) and
+    /// wrapped semiblocks `body` (`C[1]..C[n]`) and write result to `buf`.
+    ///
+    /// This is the inverse of [`AesKw::wrap_key_detached`]. The integrity check
+    /// is performed in the same way as in [`AesKw::unwrap_key`].
+    ///
+    /// Length of `body` must be multiple of [`IV_LEN`] and bigger than zero.
+    /// Length of `buf` must be bigger or equal to `body.len()`.
+    #[inline]
+    pub fn unwrap_key_detached<'a>(
+        &self,
+        iv: &[u8; IV_LEN],
+        body: &[u8],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        let expected_len = check_unwrap_len(body.len() + IV_LEN, buf.len())?;
+        let buf = &mut buf[..expected_len];
+
+        self.unwrap_detached_trusted(iv, body, buf)
+            .map_err(|_| Error::IntegrityCheckFailed)
+    }
+
+    /// Unwrap every key in `wkeys` and write results to the corresponding buffers in `bufs`.
+    ///
+    /// Lengths of all wrapped keys and buffers are validated before any unwrapping
+    /// takes place. A missing buffer is treated as an empty one, while excess buffers
+    /// are ignored.
+    ///
+    /// The returned [`BatchError`] contains index of the key which failed validation
+    /// or the integrity check. In the latter case buffers of the preceding keys
+    /// contain successfully unwrapped keys, while the remaining buffers are left untouched.
+    pub fn unwrap_keys(&self, wkeys: &[&[u8]], bufs: &mut [&mut [u8]]) -> Result<(), BatchError> {
+        for (index, wkey) in wkeys.iter().enumerate() {
+            let buf_len = bufs.get(index).map_or(0, |buf| buf.len());
+            check_unwrap_len(wkey.len(), buf_len).map_err(|error| BatchError { index, error })?;
+        }
+
+        let mut failed = None;
+        self.cipher.decrypt_with_backend(UnwrapBatchCtx {
+            iv: &AES_KW_IV,
+            wkeys,
+            bufs,
+            failed: &mut failed,
+        });
+
+        match failed {
+            None => Ok(()),
+            Some(index) => Err(BatchError {
+                index,
+                error: Error::IntegrityCheckFailed,
+            }),
+        }
+    }
+
+    /// Apply the unwrapping function `W^-1` defined in NIST SP 800-38F § 6.1 to `data`
+    /// using `a` as `C[0]`.
+    ///
+    /// On input `a || data` forms the input string `C`, with `data` containing
+    /// semiblocks `C[1]..C[n]`. On output `a` holds the recovered integrity register `A`
+    /// and `data` holds `R[1]..R[n]`. No integrity check is performed, it's
+    /// the caller's responsibility to verify the value of `a`.
+    ///
+    /// Length of `data` must be multiple of [`IV_LEN`] and bigger than zero.
+    /// SP 800-38F defines `W^-1` only for 2 or more semiblocks, but a single semiblock
+    /// is accepted as well for consistency with [`AesKw::unwrap_key`], which supports
+    /// 8-byte keys. Callers which need strict SP 800-38F conformance must check
+    /// that `data` is at least `2 * IV_LEN` bytes long.
+    #[inline]
+    pub fn unwrap_semiblocks(&self, a: &mut [u8; IV_LEN], data: &mut [u8]) -> Result<(), Error> {
+        if data.is_empty() || data.len() % IV_LEN != 0 {
+            return Err(Error::InvalidDataSize);
+        }
+
+        let block = &mut Block::<C>::default();
+        block[..IV_LEN].copy_from_slice(a);
+
+        self.cipher.decrypt_with_backend(Ctx {
+            blocks_len: data.len() / IV_LEN,
+            block,
+            buf: data,
+        });
+
+        a.copy_from_slice(&block[..IV_LEN]);
+        Ok(())
+    }
+
+    /// Unwrap `wkey` wrapped using OpenPGP ECDH padding and write result to `buf`.
+    ///
+    /// This is the inverse of [`AesKw::wrap_pgp`]. The PKCS#5 padding defined
+    /// in RFC 6637 § 8 is validated and stripped after unwrapping.
+    ///
+    /// Returns slice which points to `buf` and contains unwrapped data.
+    /// Returns [`Error::IntegrityCheckFailed`] if the padding is malformed.
+    ///
+    /// Length of `wkey` must be multiple of [`IV_LEN`] and bigger than [`IV_LEN`].
+    /// Length of `buf` must be bigger or equal to `wkey.len() - IV_LEN`.
+    #[inline]
+    pub fn unwrap_pgp<'a>(&self, wkey: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        let padded_len = self.unwrap_key(wkey, buf)?.len();
+        let padded = &mut buf[..padded_len];
+
+        // `padded_len` is always bigger or equal to `IV_LEN`
+        let pad_len = usize::from(padded[padded_len - 1]);
+        if pad_len == 0
+            || pad_len > IV_LEN
+            || padded[padded_len - pad_len..]
+                .iter()
+                .any(|&b| usize::from(b) != pad_len)
+        {
+            padded.fill(0);
+            return Err(Error::IntegrityCheckFailed);
+        }
+
+        Ok(&buf[..padded_len - pad_len])
+    }
+
     /// Unwrap key in `data` and return unwrapped key.
     ///
     /// This method is roughly equivalent to:
@@ -209,6 +1047,8 @@ impl<C: BlockCipherDecrypt<BlockSize = U16>> AesKw<C> {
     /// ```
     /// but uses [`hybrid_array::Array`][Array] instead of built-in arrays
     /// to work around current limitations of the const generics system.
+    /// Built-in arrays can be converted to and from [`Array`] using
+    /// the [`From`]/[`Into`] traits.
     #[inline]
     pub fn unwrap_fixed_key<N>(
         &self,
@@ -223,4 +1063,254 @@ impl<C: BlockCipherDecrypt<BlockSize = U16>> AesKw<C> {
         self.unwrap_key_trusted(wkey, &mut buf)?;
         Ok(buf)
     }
+
+    /// Unwrap key in `data` and return unwrapped key which gets zeroized on drop.
+    ///
+    /// Same as [`AesKw::unwrap_fixed_key`], but the key is written directly
+    /// into [`SecretKey`].
+    #[cfg(feature = "zeroize")]
+    #[inline]
+    pub fn unwrap_fixed_key_secret<N>(
+        &self,
+        wkey: &KwWrappedKey<N>,
+    ) -> Result<SecretKey<N>, IntegrityCheckFailed>
+    where
+        N: ArraySize + NonZero + Add<IvLen> + Rem<IvLen>,
+        Sum<N, IvLen>: ArraySize,
+        Mod<N, IvLen>: Zero,
+    {
+        let mut key = SecretKey(Array::default());
+        self.unwrap_key_trusted(wkey, &mut key.0)?;
+        Ok(key)
+    }
+
+    /// Unwrap key in `wkey` and return it as a key for cipher `C2`.
+    ///
+    /// Same as [`AesKw::unwrap_fixed_key`], but the size of `wkey` is derived
+    /// from key size of `C2`, so a mismatch is caught at compile time.
+    /// The result can be passed directly to [`KeyInit::new`].
+    #[inline]
+    pub fn unwrap_fixed_key_as<C2>(
+        &self,
+        wkey: &KwWrappedKey<C2::KeySize>,
+    ) -> Result<Key<C2>, IntegrityCheckFailed>
+    where
+        C2: KeySizeUser,
+        C2::KeySize: NonZero + Add<IvLen> + Rem<IvLen>,
+        Sum<C2::KeySize, IvLen>: ArraySize,
+        Mod<C2::KeySize, IvLen>: Zero,
+    {
+        self.unwrap_fixed_key::<C2::KeySize>(wkey)
+    }
+
+    /// Unwrap key in `wkey` and return unwrapped key stored in a built-in array.
+    ///
+    /// Same as [`AesKw::unwrap_fixed_key`], but uses built-in arrays instead of [`Array`].
+    /// Lengths are checked at compile time, see [`AesKw::wrap_array`] for details.
+    #[inline]
+    pub fn unwrap_array<const N: usize, const M: usize>(
+        &self,
+        wkey: &[u8; M],
+    ) -> Result<[u8; N], IntegrityCheckFailed> {
+        const { check_array_lens(N, M) };
+        let mut buf = [0u8; N];
+        self.unwrap_key_trusted(wkey, &mut buf)?;
+        Ok(buf)
+    }
+
+    /// Unwrap key in `wkey` in place and return reference to the unwrapped key.
+    ///
+    /// Same as [`AesKw::unwrap_fixed_key`], but does not use an intermediate
+    /// buffer, which reduces stack usage for big keys. The unwrapped key is stored
+    /// in the last `N` bytes of `wkey`. On integrity check failure `wkey` is zeroed.
+    #[inline]
+    pub fn unwrap_fixed_key_in_place<'a, N>(
+        &self,
+        wkey: &'a mut KwWrappedKey<N>,
+    ) -> Result<&'a Array<u8, N>, IntegrityCheckFailed>
+    where
+        N: ArraySize + NonZero + Add<IvLen> + Rem<IvLen>,
+        Sum<N, IvLen>: ArraySize,
+        Mod<N, IvLen>: Zero,
+    {
+        let (iv, body) = wkey.split_at_mut(IV_LEN);
+        self.unwrap_in_place_trusted(iv, body)?;
+        Ok((&*body).try_into().unwrap())
+    }
+
+    /// Unwrap `wkey` into a possibly uninitialized buffer `buf`.
+    ///
+    /// Returns initialized slice which points to `buf` and contains unwrapped key.
+    /// Length requirements are the same as in [`AesKw::unwrap_key`].
+    /// On integrity check failure the output part of `buf` is zeroed.
+    ///
+    /// The ciphertext is copied directly into the uninitialized slots and then
+    /// unwrapped in place, so the output buffer is written only once. The price is
+    /// the only `unsafe` block of this crate, which casts the initialized slots
+    /// to `&mut [u8]`. Use [`AesKw::unwrap_key`] with a zeroed buffer if the extra
+    /// pass over the output is not a concern.
+    #[allow(unsafe_code)]
+    #[inline]
+    pub fn unwrap_key_uninit<'a>(
+        &self,
+        wkey: &[u8],
+        buf: &'a mut [MaybeUninit<u8>],
+    ) -> Result<&'a [u8], Error> {
+        let expected_len = check_unwrap_len(wkey.len(), buf.len())?;
+        let (iv, body) = wkey.split_at(IV_LEN);
+        let buf = &mut buf[..expected_len];
+        for (dst, &src) in buf.iter_mut().zip(body) {
+            dst.write(src);
+        }
+        // SAFETY: `body` has the same length as `buf`, so all elements of `buf`
+        // were initialized above and `MaybeUninit<u8>` has the same layout as `u8`.
+        let buf = unsafe { &mut *(buf as *mut [MaybeUninit<u8>] as *mut [u8]) };
+
+        let mut iv: [u8; IV_LEN] = iv.try_into().unwrap();
+        self.unwrap_in_place_trusted(&mut iv, buf)
+            .map_err(|_| Error::IntegrityCheckFailed)?;
+        Ok(buf)
+    }
+
+    /// Unwrap key stored in `buf` in place.
+    ///
+    /// Returns slice which points to `buf` and contains unwrapped key stored
+    /// in the last `buf.len() - IV_LEN` bytes of `buf`. Unlike [`AesKw::unwrap_key`],
+    /// the wrapped key is not copied into a separate buffer, so this method is
+    /// preferable when the wrapped key does not have to be preserved.
+    /// On integrity check failure `buf` is zeroed.
+    ///
+    /// Length of `buf` must be multiple of [`IV_LEN`] and bigger than [`IV_LEN`].
+    #[inline]
+    pub fn unwrap_key_in_place<'a>(&self, buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        check_unwrap_len(buf.len(), usize::MAX)?;
+
+        let (iv, body) = buf.split_at_mut(IV_LEN);
+        self.unwrap_in_place_trusted(iv, body)
+            .map_err(|_| Error::IntegrityCheckFailed)?;
+        Ok(body)
+    }
+
+    /// Unwrap semiblocks `body` in place using integrity register `iv`.
+    ///
+    /// Both `iv` and `body` are zeroed on integrity check failure.
+    fn unwrap_in_place_trusted(
+        &self,
+        iv: &mut [u8],
+        body: &mut [u8],
+    ) -> Result<(), IntegrityCheckFailed> {
+        let block = &mut Block::<C>::default();
+        block[..IV_LEN].copy_from_slice(iv);
+
+        self.cipher.decrypt_with_backend(Ctx {
+            blocks_len: body.len() / IV_LEN,
+            block,
+            buf: body,
+        });
+
+        let expected_iv = u64::from_ne_bytes(AES_KW_IV);
+        let calc_iv = u64::from_ne_bytes(block[..IV_LEN].try_into().unwrap());
+        if calc_iv == expected_iv {
+            Ok(())
+        } else {
+            iv.fill(0);
+            body.fill(0);
+            Err(IntegrityCheckFailed)
+        }
+    }
+
+    /// Decode unpadded base64url string `s` and return unwrapped key.
+    ///
+    /// Returns [`Error::InvalidEncoding`] if `s` is not a valid unpadded base64url string.
+    #[cfg(feature = "base64")]
+    pub fn unwrap_key_b64(&self, s: &str) -> Result<Vec<u8>, Error> {
+        let wkey = Base64UrlUnpadded::decode_vec(s).map_err(|_| Error::InvalidEncoding)?;
+        let mut buf = vec![0u8; wkey.len().saturating_sub(IV_LEN)];
+        self.unwrap_key(&wkey, &mut buf)?;
+        Ok(buf)
+    }
+
+    /// Unwrap `wkey` and return unwrapped key as [`Bytes`].
+    ///
+    /// Note that [`Bytes`] does not zeroize its contents on drop, so the caller
+    /// is responsible for the lifetime of the unwrapped key in memory.
+    /// On integrity check failure the intermediate buffer is zeroed.
+    ///
+    /// Length requirements for `wkey` are the same as in [`AesKw::unwrap_key`].
+    #[cfg(feature = "bytes")]
+    pub fn unwrap_key_bytes(&self, wkey: &[u8]) -> Result<Bytes, Error> {
+        let mut buf = BytesMut::zeroed(Self::required_unwrap_len(wkey.len())?);
+        self.unwrap_key_trusted(wkey, &mut buf)?;
+        Ok(buf.freeze())
+    }
+}
+
+impl<C: BlockCipherEncrypt<BlockSize = U16>> KeyWrapEncrypt for AesKw<C> {
+    fn iv_len(&self) -> usize {
+        IV_LEN
+    }
+
+    fn wrap_len(&self, key_len: usize) -> Result<usize, Error> {
+        Self::required_wrap_len(key_len)
+    }
+
+    fn wrap_key_default<'a>(&self, key: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        self.wrap_key(key, buf)
+    }
+
+    fn wrap_key_with_iv<'a>(
+        &self,
+        key: &[u8],
+        iv: &[u8],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        let iv = fixed_iv::<IV_LEN>(iv)?;
+        let expected_len = Self::required_wrap_len(key.len())?;
+        let buf = buf
+            .get_mut(..expected_len)
+            .ok_or(Error::InvalidOutputSize { expected_len })?;
+
+        let (a, body) = buf.split_at_mut(IV_LEN);
+        a.copy_from_slice(iv);
+        body.copy_from_slice(key);
+        self.wrap_semiblocks(a.try_into().unwrap(), body)?;
+        Ok(buf)
+    }
+}
+
+impl<C: BlockCipherDecrypt<BlockSize = U16>> KeyWrapDecrypt for AesKw<C> {
+    fn iv_len(&self) -> usize {
+        IV_LEN
+    }
+
+    fn unwrap_len(&self, wkey_len: usize) -> Result<usize, Error> {
+        Self::required_unwrap_len(wkey_len)
+    }
+
+    fn unwrap_key_default<'a>(&self, wkey: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        self.unwrap_key(wkey, buf)
+    }
+
+    fn unwrap_key_with_iv<'a>(
+        &self,
+        wkey: &[u8],
+        iv: &[u8],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        let iv = fixed_iv::<IV_LEN>(iv)?;
+        let expected_len = check_unwrap_len(wkey.len(), buf.len())?;
+        let buf = &mut buf[..expected_len];
+
+        let (a, body) = wkey.split_at(IV_LEN);
+        let mut a: [u8; IV_LEN] = a.try_into().unwrap();
+        buf.copy_from_slice(body);
+        self.unwrap_semiblocks(&mut a, buf)?;
+
+        if u64::from_ne_bytes(a) == u64::from_ne_bytes(*iv) {
+            Ok(buf)
+        } else {
+            buf.fill(0);
+            Err(Error::IntegrityCheckFailed)
+        }
+    }
 }
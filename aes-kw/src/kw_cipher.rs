@@ -0,0 +1,37 @@
+//! Block size bound for the KEK ciphers
+use aes::cipher::{crypto_common::BlockSizeUser, typenum::U16};
+
+/// Block cipher which can be used as a key-encryption key cipher.
+///
+/// Both AES-KW and AES-KWP are defined only for ciphers with 128-bit blocks,
+/// i.e. with [`BlockSize`][BlockSizeUser::BlockSize] equal to `U16`. This trait
+/// is implemented for all such ciphers and is used as a bound on [`AesKw`][crate::AesKw]
+/// and [`AesKwp`][crate::AesKwp], so naming them with a cipher with a different
+/// block size results in a compilation error. It can not be implemented outside
+/// of this crate.
+///
+/// The trait intentionally does not require `BlockCipherEncrypt + BlockCipherDecrypt`,
+/// so encrypt-only and decrypt-only ciphers can be used as well, e.g. with the
+/// [`KwAes128Enc`][crate::KwAes128Enc] and [`KwAes128Dec`][crate::KwAes128Dec] aliases.
+/// Wrapping and unwrapping methods have their own encryption and decryption bounds.
+pub trait KwCipher: BlockSizeUser + sealed::Sealed {}
+
+impl<C: BlockSizeUser> KwCipher for C where C::BlockSize: sealed::Is128 {}
+
+mod sealed {
+    use super::*;
+
+    pub trait Sealed {}
+
+    impl<C: BlockSizeUser> Sealed for C where C::BlockSize: Is128 {}
+
+    /// Marker implemented only for `U16`, used to produce a readable error
+    /// for ciphers with a different block size.
+    #[diagnostic::on_unimplemented(
+        message = "key wrapping requires a block cipher with 128-bit blocks",
+        label = "cipher block size is not 128 bits"
+    )]
+    pub trait Is128 {}
+
+    impl Is128 for U16 {}
+}
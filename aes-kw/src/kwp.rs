@@ -1,59 +1,336 @@
-use core::ops::{Add, Div, Mul};
+use core::{
+    fmt,
+    ops::{Add, Div, Mul},
+};
 
-use crate::{ctx::Ctx, Error, IntegrityCheckFailed, IvLen, IV_LEN};
+use crate::{
+    ctx::Ctx,
+    traits::{fixed_iv, KeyWrapDecrypt, KeyWrapEncrypt},
+    Error, IntegrityCheckFailed, IvLen, KwCipher, IV_LEN,
+};
 use aes::cipher::{
     array::ArraySize,
     consts::{B1, U4294967296, U7},
-    crypto_common::{InnerInit, InnerUser},
+    crypto_common::{BlockSizeUser, InnerInit, InnerUser},
     typenum::{Add1, IsLess, Le, NonZero, Prod, Quot, Sum, U16},
-    Array, Block, BlockCipherDecrypt, BlockCipherEncrypt,
+    Array, Block, BlockCipherDecrypt, BlockCipherEncrypt, Key, KeyInit,
 };
 
+#[cfg(feature = "zeroize")]
+use crate::SecretKey;
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "rand_core")]
+use rand_core::CryptoRngCore;
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+#[cfg(feature = "zeroize")]
+use zeroize::Zeroize;
+
 /// Maximum length of the AES-KWP input data (2^32 bytes) represented as a `typenum` type.
 type KwpMaxLen = U4294967296;
 /// Maximum length of the AES-KWP input data (2^32 - 1 bytes).
-const KWP_MAX_LEN: usize = u32::MAX as usize;
+///
+/// On targets with `usize` narrower than 32 bits the length is limited by `usize::MAX`.
+const KWP_MAX_LEN: usize = if usize::BITS < u32::BITS {
+    usize::MAX
+} else {
+    u32::MAX as usize
+};
 
-/// Alternative Initial Value constant prefix for AES-KWP as defined in RFC 3394 § 3.
+/// Alternative Initial Value constant prefix for AES-KWP as defined in RFC 5649 § 3.
 ///
 /// <https://datatracker.ietf.org/doc/html/rfc5649#section-3>
 ///
 /// ```text
 /// The Alternative Initial Value (AIV) required by this specification is
-//  a 32-bit constant concatenated to a 32-bit MLI.  The constant is (in
-//  hexadecimal) A65959A6 and occupies the high-order half of the AIV.
+/// a 32-bit constant concatenated to a 32-bit MLI.  The constant is (in
+/// hexadecimal) A65959A6 and occupies the high-order half of the AIV.
 /// ```
-const KWP_IV_PREFIX: [u8; IV_LEN / 2] = [0xA6, 0x59, 0x59, 0xA6];
+pub const KWP_AIV_PREFIX: [u8; IV_LEN / 2] = [0xA6, 0x59, 0x59, 0xA6];
 
 /// [`IvLen`] (`U8`) minus one
 type IvLenM1 = U7;
 
 /// Type alias representing wrapped key roughly equivalent to
 /// `[u8; IV_LEN * (N.div_ceil(IV_LEN) + 1)]`.
+///
+/// Return type of [`AesKwp::wrap_fixed_key`], which can be used to store
+/// the wrapped key in a struct field:
+///
+/// ```
+/// use aes_kw::{cipher::consts::U20, KeyInit, KwpAes128, KwpWrappedKey};
+///
+/// struct StoredKey {
+///     wkey: KwpWrappedKey<U20>,
+/// }
+///
+/// let kwp = KwpAes128::new(&[0x42; 16].into());
+/// let stored = StoredKey {
+///     wkey: kwp.wrap_fixed_key::<U20>(&[0x24; 20].into()),
+/// };
+/// assert_eq!(stored.wkey.len(), 32);
+/// ```
 pub type KwpWrappedKey<N> = Array<u8, Prod<Add1<Quot<Sum<N, IvLenM1>, IvLen>>, IvLen>>;
 
 /// AES Key Wrapper with Padding (KWP), as defined in [RFC 5649].
 ///
 /// [RFC 5649]: https://www.rfc-editor.org/rfc/rfc5649.txt
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub struct AesKwp<C> {
+#[derive(Clone, Copy, PartialEq)]
+pub struct AesKwp<C: KwCipher> {
     cipher: C,
 }
 
-impl<C> InnerUser for AesKwp<C> {
+/// [`AesKwp`] which borrows the inner block cipher.
+///
+/// It allows using a pre-initialized cipher (e.g. from a pool of KEKs) without
+/// moving or cloning it. All wrap and unwrap methods of [`AesKwp`] are available,
+/// since the block cipher traits are implemented for references to ciphers.
+/// It can be created using [`AesKwp::with_cipher_ref`].
+pub type AesKwpRef<'a, C> = AesKwp<&'a C>;
+
+impl<C: KwCipher> fmt::Debug for AesKwp<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("AesKwp { ... }")
+    }
+}
+
+/// Check length of the wrapped key for unwrapping.
+///
+/// Returns length of the padded unwrapped key.
+fn check_unwrap_len(wkey_len: usize) -> Result<usize, Error> {
+    let blocks_len = wkey_len / IV_LEN;
+    if wkey_len % IV_LEN != 0 {
+        return Err(Error::NotSemiblockAligned);
+    }
+    if blocks_len < 2 {
+        return Err(Error::TooShort { min: 2 * IV_LEN });
+    }
+    if wkey_len > KWP_MAX_LEN {
+        return Err(Error::InvalidDataSize);
+    }
+    Ok((blocks_len - 1) * IV_LEN)
+}
+
+/// Check that keys of `key_len` bytes are supported and wrapped into `wkey_len` bytes.
+///
+/// Used in const contexts, so it fails compilation on unsupported lengths.
+const fn check_array_lens(key_len: usize, wkey_len: usize) {
+    assert!(
+        key_len != 0 && key_len as u64 <= u32::MAX as u64,
+        "key length is not supported by AES-KWP"
+    );
+    assert!(
+        wkey_len == IV_LEN * (key_len.div_ceil(IV_LEN) + 1),
+        "wrapped key length does not match key length"
+    );
+}
+
+/// Check recovered `aiv` and padding of the unwrapped key stored in `buf`.
+fn check_aiv<'a>(
+    aiv: &[u8; IV_LEN],
+    aiv_prefix: &[u8; IV_LEN / 2],
+    buf: &'a mut [u8],
+) -> Result<&'a [u8], IntegrityCheckFailed> {
+    let blocks_len = buf.len() / IV_LEN;
+
+    // 2) AIV verification
+
+    // Checks as defined in RFC5649 § 3
+    //
+    // All checks are accumulated into `bad` and the whole `buf` is processed
+    // regardless of the MLI value, so memory access patterns do not depend on it.
+
+    let prefix_calc = u32::from_ne_bytes(aiv[..IV_LEN / 2].try_into().unwrap());
+    let prefix_exp = u32::from_ne_bytes(*aiv_prefix);
+    let mut bad = u64::from(prefix_calc ^ prefix_exp);
+
+    let mli_bytes = aiv[IV_LEN / 2..].try_into().unwrap();
+    let mli = u64::from(u32::from_be_bytes(mli_bytes));
+    bad |= mli.div_ceil(IV_LEN as u64) ^ (blocks_len as u64);
+
+    // The padding length must be smaller than a semiblock, i.e.
+    // `8 * (n - 1) < MLI <= 8 * n`. It's implied by the check above,
+    // but RFC 5649 § 3 defines it explicitly, so we check it as well.
+    // If `MLI > 8 * n`, the subtraction wraps around and the result is non-zero.
+    let pad_len = (blocks_len as u64 * IV_LEN as u64).wrapping_sub(mli);
+    bad |= pad_len / IV_LEN as u64;
+
+    // RFC 5649 § 3 requires `MLI >= 1`. Zero MLI is rejected by the `div_ceil`
+    // check above since `n >= 1`, but we reject it explicitly to not rely on it.
+    bad |= u64::from(mli == 0);
+
+    // Bytes at positions `i >= mli` must be zero. The mask is computed from
+    // the sign bit of `mli - i - 1`, which can not overflow since both values
+    // are smaller than 2^63.
+    let mut pad_acc = 0u8;
+    for (i, &b) in buf.iter().enumerate() {
+        let is_pad = (mli.wrapping_sub(i as u64 + 1) >> 63) as u8;
+        pad_acc |= b & is_pad.wrapping_neg();
+    }
+    bad |= u64::from(pad_acc);
+
+    if bad != 0 {
+        buf.fill(0);
+        return Err(IntegrityCheckFailed);
+    }
+
+    // `mli` is not bigger than `buf.len()` after the checks above
+    Ok(&buf[..mli as usize])
+}
+
+impl<C: KwCipher> InnerUser for AesKwp<C> {
     type Inner = C;
 }
 
-impl<C> InnerInit for AesKwp<C> {
+impl<C: KwCipher> BlockSizeUser for AesKwp<C> {
+    type BlockSize = C::BlockSize;
+}
+
+impl<C: KwCipher> InnerInit for AesKwp<C> {
     #[inline]
     fn inner_init(cipher: Self::Inner) -> Self {
         AesKwp { cipher }
     }
 }
 
+impl<C: KwCipher> AesKwp<C> {
+    /// Length of a semiblock in bytes, i.e. half of the cipher block.
+    pub const SEMIBLOCK_LEN: usize = IV_LEN;
+    /// Number of bytes added to a key by wrapping, not counting the padding.
+    ///
+    /// The key is padded with up to `IV_LEN - 1` zero bytes to a multiple of [`IV_LEN`].
+    pub const OVERHEAD: usize = IV_LEN;
+    /// Minimum supported length of a key in bytes.
+    pub const MIN_KEY_LEN: usize = 1;
+    /// Maximum supported length of a key in bytes.
+    ///
+    /// The message length indicator is 32-bit, so the limit is 2^32 - 1 bytes.
+    /// On targets with `usize` narrower than 32 bits it's limited by `usize::MAX`.
+    pub const MAX_KEY_LEN: usize = KWP_MAX_LEN;
+
+    /// Get length of the wrapped key for a key of length `key_len`.
+    ///
+    /// The key gets padded to a multiple of [`IV_LEN`], so the result is
+    /// the smallest multiple of [`IV_LEN`] which is at least [`IV_LEN`] bytes
+    /// bigger than `key_len`. Returns [`Error::InvalidDataSize`] if `key_len`
+    /// is zero or bigger than [`Self::MAX_KEY_LEN`] and [`Error::LengthOverflow`]
+    /// if the wrapped length does not fit into `usize` (e.g. for keys close
+    /// to 2^32 bytes on 32-bit targets).
+    #[inline]
+    pub fn required_wrap_len(key_len: usize) -> Result<usize, Error> {
+        if !(Self::MIN_KEY_LEN..=Self::MAX_KEY_LEN).contains(&key_len) {
+            return Err(Error::InvalidDataSize);
+        }
+        // number of 64 bit blocks in the input data (padded)
+        let semiblocks_len = key_len.div_ceil(IV_LEN);
+        semiblocks_len
+            .checked_add(1)
+            .and_then(|n| n.checked_mul(IV_LEN))
+            .ok_or(Error::LengthOverflow)
+    }
+
+    /// Check whether a key of length `key_len` is wrapped using a single block
+    /// cipher invocation.
+    ///
+    /// As defined in RFC 5649 § 4.1, keys of up to [`IV_LEN`] bytes are padded and
+    /// encrypted together with the AIV as a single block instead of using
+    /// the wrapping process W.
+    #[inline]
+    pub fn is_single_block(key_len: usize) -> bool {
+        (Self::MIN_KEY_LEN..=IV_LEN).contains(&key_len)
+    }
+
+    /// Get length of the buffer required for unwrapping a wrapped key of length `wkey_len`.
+    ///
+    /// Note that the unwrapped key may be up to `IV_LEN - 1` bytes shorter than
+    /// the returned value because of the padding. Returns [`Error::NotSemiblockAligned`]
+    /// if `wkey_len` is not a multiple of [`IV_LEN`], [`Error::TooShort`] if it's
+    /// shorter than two semiblocks, and [`Error::InvalidDataSize`] if it's too big.
+    #[inline]
+    pub fn required_unwrap_len(wkey_len: usize) -> Result<usize, Error> {
+        check_unwrap_len(wkey_len)
+    }
+
+    /// Get reference to the inner block cipher.
+    #[inline]
+    pub fn get_inner(&self) -> &C {
+        &self.cipher
+    }
+}
+
+impl<C: BlockSizeUser<BlockSize = U16>> AesKwp<C> {
+    /// Create wrapper which borrows `cipher` instead of taking ownership of it.
+    #[inline]
+    pub fn with_cipher_ref(cipher: &C) -> AesKwpRef<'_, C> {
+        AesKwp { cipher }
+    }
+}
+
+impl<C: KwCipher + KeyInit> AesKwp<C> {
+    /// Reinitialize the inner block cipher with a new key-encryption key.
+    ///
+    /// The old cipher state gets dropped in place. With the `zeroize` feature enabled
+    /// the block ciphers re-exported by this crate zeroize their key schedule on drop.
+    #[inline]
+    pub fn rekey(&mut self, key: &Key<C>) {
+        self.cipher = C::new(key);
+    }
+
+    /// Initialize wrapper with a random key-encryption key generated using `rng`.
+    ///
+    /// The temporary key buffer is zeroized after the cipher is initialized,
+    /// so the `rand_core` feature also enables `zeroize`.
+    #[cfg(feature = "rand_core")]
+    #[inline]
+    pub fn generate<R: CryptoRngCore + ?Sized>(rng: &mut R) -> Self {
+        let mut key = Key::<C>::default();
+        rng.fill_bytes(&mut key);
+        let res = Self::new(&key);
+        key.zeroize();
+        res
+    }
+
+    /// Initialize wrapper with a random key-encryption key generated using
+    /// the operating system's secure RNG.
+    ///
+    /// The temporary key buffer is zeroized after the cipher is initialized,
+    /// so the `getrandom` feature also enables `zeroize`.
+    #[cfg(feature = "getrandom")]
+    #[inline]
+    pub fn generate_kek() -> Result<Self, getrandom::Error> {
+        let mut key = Key::<C>::default();
+        let res = getrandom::getrandom(&mut key).map(|()| Self::new(&key));
+        key.zeroize();
+        res
+    }
+}
+
+impl<C: KwCipher> AsRef<C> for AesKwp<C> {
+    #[inline]
+    fn as_ref(&self) -> &C {
+        &self.cipher
+    }
+}
+
+impl<C: KwCipher + KeyInit> TryFrom<&[u8]> for AesKwp<C> {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(value: &[u8]) -> Result<Self, Error> {
+        C::new_from_slice(value)
+            .map(Self::inner_init)
+            .map_err(|_| Error::InvalidKekSize {
+                bits: value.len() * 8,
+            })
+    }
+}
+
 impl<C: BlockCipherEncrypt<BlockSize = U16>> AesKwp<C> {
-    /// Wrap key into `buf` assuming that it has correct length.
-    fn wrap_key_trusted(&self, key: &[u8], buf: &mut [u8]) {
+    /// Wrap key into `buf` with AIV prefix `aiv_prefix` assuming that it has correct length.
+    fn wrap_key_trusted(&self, key: &[u8], aiv_prefix: &[u8; IV_LEN / 2], buf: &mut [u8]) {
         let semiblocks_len = key.len().div_ceil(IV_LEN);
 
         // 2) Wrapping
@@ -63,7 +340,7 @@ impl<C: BlockCipherEncrypt<BlockSize = U16>> AesKwp<C> {
         // Set A to the AIV
         let block = &mut Block::<C>::default();
         let (prefix, mli) = block[..IV_LEN].split_at_mut(IV_LEN / 2);
-        prefix.copy_from_slice(&KWP_IV_PREFIX);
+        prefix.copy_from_slice(aiv_prefix);
         // 32-bit MLI equal to the number of bytes in the input data, big endian
         mli.copy_from_slice(&(key.len() as u32).to_be_bytes());
 
@@ -77,13 +354,16 @@ impl<C: BlockCipherEncrypt<BlockSize = U16>> AesKwp<C> {
         } else {
             // 1) Append padding
 
+            let (data, pad) = buf[IV_LEN..].split_at_mut(key.len());
+            data.copy_from_slice(key);
+            pad.fill(0);
+
             // 2.2) Calculate intermediate values
-            buf[IV_LEN..][..key.len()].copy_from_slice(key);
 
             self.cipher.encrypt_with_backend(Ctx {
                 blocks_len: semiblocks_len,
                 block,
-                buf,
+                buf: &mut buf[IV_LEN..],
             });
 
             // 2.3) Output the results
@@ -93,28 +373,95 @@ impl<C: BlockCipherEncrypt<BlockSize = U16>> AesKwp<C> {
 
     /// AES Key Wrap with Padding, as defined in RFC 5649.
     ///
+    /// Length of `key` must be bigger than zero and smaller than 2^32 bytes.
+    ///
     /// The `buf` buffer will be overwritten, and must be the smallest
     /// multiple of [`IV_LEN`] (i.e. 8) which is at least [`IV_LEN`]
     /// bytes (i.e. 8 bytes) longer than the length of `data`.
     #[inline]
     pub fn wrap_key<'a>(&self, key: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
-        if key.len() > KWP_MAX_LEN {
-            return Err(Error::InvalidDataSize);
-        }
+        let n = self.wrap_key_into(key, buf)?;
+        Ok(&buf[..n])
+    }
 
-        // 0) Prepare inputs
+    /// AES Key Wrap with Padding, as defined in RFC 5649.
+    ///
+    /// Same as [`AesKwp::wrap_key`], but accepts any type which can be
+    /// referenced as a byte slice, e.g. `Vec<u8>` or `[u8; N]`.
+    #[inline]
+    pub fn wrap_key_ref<'a, K: AsRef<[u8]>>(
+        &self,
+        key: K,
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        self.wrap_key(key.as_ref(), buf)
+    }
 
-        // number of 64 bit blocks in the input data (padded)
-        let semiblocks_len = key.len().div_ceil(IV_LEN);
+    /// AES Key Wrap with Padding, as defined in RFC 5649, followed by zeroization of `key`.
+    ///
+    /// Same as [`AesKwp::wrap_key`], but wipes the caller's plaintext buffer
+    /// after successful wrapping. Note that this method mutates the input.
+    /// On error `key` is left untouched.
+    #[cfg(feature = "zeroize")]
+    #[inline]
+    pub fn wrap_key_consuming<'a>(
+        &self,
+        key: &mut [u8],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        let res = self.wrap_key(key, buf)?;
+        key.zeroize();
+        Ok(res)
+    }
 
-        let expected_len = semiblocks_len * IV_LEN + IV_LEN;
+    /// AES Key Wrap with Padding, as defined in RFC 5649.
+    ///
+    /// Returns number of bytes written to `buf`.
+    ///
+    /// Length requirements for `key` and `buf` are the same as in [`AesKwp::wrap_key`].
+    #[inline]
+    pub fn wrap_key_into(&self, key: &[u8], buf: &mut [u8]) -> Result<usize, Error> {
+        let expected_len = Self::required_wrap_len(key.len())?;
         let buf = buf
             .get_mut(..expected_len)
             .ok_or(Error::InvalidOutputSize { expected_len })?;
 
-        self.wrap_key_trusted(key, buf);
+        self.wrap_key_trusted(key, &KWP_AIV_PREFIX, buf);
 
-        Ok(buf)
+        Ok(expected_len)
+    }
+
+    /// Wrap `key` and append the wrapped key to `out`.
+    ///
+    /// `out` is extended by the padded wrapped key length and the key is wrapped
+    /// directly in the new tail. On error `out` is left unchanged.
+    ///
+    /// Length requirements for `key` are the same as in [`AesKwp::wrap_key`].
+    #[cfg(feature = "alloc")]
+    pub fn wrap_key_append(&self, key: &[u8], out: &mut Vec<u8>) -> Result<(), Error> {
+        let wkey_len = Self::required_wrap_len(key.len())?;
+        let start = out.len();
+        out.resize(start + wkey_len, 0);
+        self.wrap_key_trusted(key, &KWP_AIV_PREFIX, &mut out[start..]);
+        Ok(())
+    }
+
+    /// Wrap `key` and return only the first semiblock of the wrapped key.
+    ///
+    /// The key is wrapped into an internal buffer, which is discarded
+    /// afterwards. The returned value can be recorded e.g. in audit logs
+    /// as a checksum of the wrapped key.
+    ///
+    /// Note that the key can not be recovered from the returned value, so it
+    /// is not a substitute for storing the wrapped key. It also should not be
+    /// used as a MAC of `key` in other contexts.
+    ///
+    /// Length requirements for `key` are the same as in [`AesKwp::wrap_key`].
+    #[cfg(feature = "alloc")]
+    pub fn integrity_tag(&self, key: &[u8]) -> Result<[u8; IV_LEN], Error> {
+        let mut buf = vec![0u8; Self::required_wrap_len(key.len())?];
+        self.wrap_key_trusted(key, &KWP_AIV_PREFIX, &mut buf);
+        Ok(buf[..IV_LEN].try_into().unwrap())
     }
 
     /// Wrap fixed-size key `key` and return wrapped key.
@@ -129,10 +476,13 @@ impl<C: BlockCipherEncrypt<BlockSize = U16>> AesKwp<C> {
     /// ```
     /// but uses [`hybrid_array::Array`][Array] instead of built-in arrays
     /// to work around current limitations of the const generics system.
+    /// Built-in arrays can be converted to and from [`Array`] using
+    /// the [`From`]/[`Into`] traits.
     #[inline]
+    #[must_use]
     pub fn wrap_fixed_key<N>(&self, key: &Array<u8, N>) -> KwpWrappedKey<N>
     where
-        N: ArraySize + Add<IvLenM1> + IsLess<KwpMaxLen>,
+        N: ArraySize + NonZero + Add<IvLenM1> + IsLess<KwpMaxLen>,
         Le<N, KwpMaxLen>: NonZero,
         Sum<N, IvLenM1>: Div<IvLen>,
         Quot<Sum<N, IvLenM1>, IvLen>: Add<B1>,
@@ -147,19 +497,126 @@ impl<C: BlockCipherEncrypt<BlockSize = U16>> AesKwp<C> {
         let mut buf = KwpWrappedKey::<N>::default();
         assert_eq!(semiblocks_len * IV_LEN + IV_LEN, buf.len());
 
-        self.wrap_key_trusted(key, &mut buf);
+        self.wrap_key_trusted(key, &KWP_AIV_PREFIX, &mut buf);
 
         buf
     }
+
+    /// Wrap `key` and return wrapped key stored in a built-in array.
+    ///
+    /// Same as [`AesKwp::wrap_fixed_key`], but uses built-in arrays instead of [`Array`].
+    /// Length of the wrapped key is passed as a separate parameter `M`, which usually
+    /// gets inferred from the context. Both `N` being supported by AES-KWP and `M` being
+    /// equal to `IV_LEN * (N.div_ceil(IV_LEN) + 1)` are checked at compile time.
+    ///
+    /// ```
+    /// use aes_kw::{KeyInit, KwpAes256};
+    ///
+    /// let kwp = KwpAes256::new(&[0x42; 32].into());
+    /// let wkey: [u8; 40] = kwp.wrap_array(&[0x24; 30]);
+    /// let key: [u8; 30] = kwp.unwrap_array(&wkey).unwrap();
+    /// assert_eq!(key, [0x24; 30]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn wrap_array<const N: usize, const M: usize>(&self, key: &[u8; N]) -> [u8; M] {
+        const { check_array_lens(N, M) };
+        let mut buf = [0u8; M];
+        self.wrap_key_trusted(key, &KWP_AIV_PREFIX, &mut buf);
+        buf
+    }
+
+    /// Read key from `reader` until EOF, wrap it and write the wrapped key to `writer`.
+    ///
+    /// AES-KWP needs the whole key before the first byte of output can be computed,
+    /// so the key is buffered in memory, while the wrapped key is written with
+    /// a single [`Write::write_all`] call. The key is read in fixed-size chunks and
+    /// every buffer which held a part of it is zeroed before being dropped.
+    ///
+    /// Up to [`AesKwp::MAX_KEY_LEN`] bytes may be buffered before the key is rejected,
+    /// so when reading from an untrusted source limit `reader` with [`Read::take`].
+    ///
+    /// Returns number of bytes written to `writer`. Keys which are empty or longer
+    /// than 2^32 - 1 bytes result in an [`io::ErrorKind::InvalidInput`] error.
+    #[cfg(feature = "std")]
+    pub fn wrap_key_from_reader<R: Read, W: Write>(
+        &self,
+        mut reader: R,
+        mut writer: W,
+    ) -> io::Result<usize> {
+        let mut key = Vec::new();
+        let mut chunk = [0u8; 256];
+        let res = read_key(&mut reader, &mut chunk, &mut key).and_then(|()| {
+            let mut buf = vec![0u8; key.len().div_ceil(IV_LEN) * IV_LEN + IV_LEN];
+            self.wrap_key(&key, &mut buf)?;
+            Ok(buf)
+        });
+        chunk.fill(0);
+        key.fill(0);
+
+        let buf = res?;
+        writer.write_all(&buf)?;
+        Ok(buf.len())
+    }
+}
+
+/// Read `reader` until EOF into `key` using `chunk` as an intermediate buffer.
+///
+/// Instead of letting `Vec` reallocate, `key` is moved into a bigger buffer
+/// manually, so the old allocation can be zeroed before being freed.
+#[cfg(feature = "std")]
+fn read_key(reader: &mut impl Read, chunk: &mut [u8], key: &mut Vec<u8>) -> io::Result<()> {
+    loop {
+        let n = match reader.read(chunk) {
+            Ok(0) => return Ok(()),
+            Ok(n) => n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        };
+        let len = key.len() + n;
+        if len > KWP_MAX_LEN {
+            return Err(Error::InvalidDataSize.into());
+        }
+        if len > key.capacity() {
+            let mut new_key = Vec::with_capacity(len.max(2 * key.capacity()));
+            new_key.extend_from_slice(key);
+            key.fill(0);
+            *key = new_key;
+        }
+        key.extend_from_slice(&chunk[..n]);
+    }
 }
 
 impl<C: BlockCipherDecrypt<BlockSize = U16>> AesKwp<C> {
-    /// Unwrap key into `buf` assuming that it has correct length.
+    /// Unwrap key into `buf` with expected AIV prefix `aiv_prefix`
+    /// assuming that it has correct length.
     fn unwrap_key_trusted<'a>(
         &self,
         wkey: &[u8],
+        aiv_prefix: &[u8; IV_LEN / 2],
         buf: &'a mut [u8],
     ) -> Result<&'a [u8], IntegrityCheckFailed> {
+        let (iv, body) = wkey.split_at(IV_LEN);
+        //   for i = 1 to n: R[i] = C[i]
+        buf.copy_from_slice(body);
+        self.unwrap_in_place_trusted(iv, aiv_prefix, buf)
+    }
+
+    /// Unwrap key with integrity register `iv` (`C[0]`) and wrapped semiblocks
+    /// `C[1]..C[n]` stored in `buf` assuming that `buf` has correct length.
+    fn unwrap_in_place_trusted<'a>(
+        &self,
+        iv: &[u8],
+        aiv_prefix: &[u8; IV_LEN / 2],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], IntegrityCheckFailed> {
+        let aiv = self.decrypt_in_place_trusted(iv, buf);
+        check_aiv(&aiv, aiv_prefix, buf)
+    }
+
+    /// Decrypt wrapped semiblocks `C[1]..C[n]` stored in `buf` using integrity
+    /// register `iv` (`C[0]`) and return the recovered AIV without checking it.
+    fn decrypt_in_place_trusted(&self, iv: &[u8], buf: &mut [u8]) -> [u8; IV_LEN] {
         let blocks_len = buf.len() / IV_LEN;
 
         // 1) Key unwrapping
@@ -167,18 +624,14 @@ impl<C: BlockCipherDecrypt<BlockSize = U16>> AesKwp<C> {
         // 1.1) Initialize variables
 
         let block = &mut Block::<C>::default();
+        block[..IV_LEN].copy_from_slice(iv);
 
         // If n is 1, the plaintext is encrypted as a single AES block
         if blocks_len == 1 {
-            block.copy_from_slice(wkey);
+            block[IV_LEN..].copy_from_slice(buf);
             self.cipher.decrypt_block(block);
             buf.copy_from_slice(&block[IV_LEN..]);
         } else {
-            block[..IV_LEN].copy_from_slice(&wkey[..IV_LEN]);
-
-            //   for i = 1 to n: R[i] = C[i]
-            buf.copy_from_slice(&wkey[IV_LEN..]);
-
             // 1.2) Calculate intermediate values
 
             self.cipher.decrypt_with_backend(Ctx {
@@ -188,35 +641,7 @@ impl<C: BlockCipherDecrypt<BlockSize = U16>> AesKwp<C> {
             });
         }
 
-        // 2) AIV verification
-
-        // Checks as defined in RFC5649 § 3
-
-        let prefix_calc = u32::from_ne_bytes(block[..IV_LEN / 2].try_into().unwrap());
-        let prefix_exp = u32::from_ne_bytes(KWP_IV_PREFIX);
-        if prefix_calc != prefix_exp {
-            buf.fill(0);
-            return Err(IntegrityCheckFailed);
-        }
-
-        let mli_bytes = block[IV_LEN / 2..IV_LEN].try_into().unwrap();
-        let mli: usize = usize::try_from(u32::from_be_bytes(mli_bytes)).map_err(|_| {
-            buf.fill(0);
-            IntegrityCheckFailed
-        })?;
-        if mli.div_ceil(IV_LEN) != blocks_len {
-            buf.fill(0);
-            return Err(IntegrityCheckFailed);
-        }
-
-        let (res, pad) = buf.split_at_mut(mli);
-        if !pad.iter().all(|&b| b == 0) {
-            res.fill(0);
-            pad.fill(0);
-            return Err(IntegrityCheckFailed);
-        }
-
-        Ok(res)
+        block[..IV_LEN].try_into().unwrap()
     }
 
     /// AES Key Wrap with Padding, as defined in RFC 5649.
@@ -227,22 +652,85 @@ impl<C: BlockCipherDecrypt<BlockSize = U16>> AesKwp<C> {
     /// length by removing the padding.
     #[inline]
     pub fn unwrap_key<'a>(&self, data: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
-        let blocks_len = data.len() / IV_LEN;
-        let blocks_rem = data.len() % IV_LEN;
-        if blocks_rem != 0 || blocks_len < 1 || data.len() > KWP_MAX_LEN {
-            return Err(Error::InvalidDataSize);
-        }
+        let expected_len = check_unwrap_len(data.len())?;
+        let buf = buf
+            .get_mut(..expected_len)
+            .ok_or(Error::InvalidOutputSize { expected_len })?;
+
+        self.unwrap_key_trusted(data, &KWP_AIV_PREFIX, buf)
+            .map_err(|_| Error::IntegrityCheckFailed)
+    }
 
-        let blocks_len = blocks_len - 1;
-        let expected_len = blocks_len * IV_LEN;
+    /// Unwrap `wkey` and write result to `buf`, reporting the recovered
+    /// AIV on failure.
+    ///
+    /// Same as [`AesKwp::unwrap_key`], but on integrity check failure returns
+    /// [`Error::IntegrityCheckFailedDebug`] with the computed AIV, which helps
+    /// to diagnose e.g. a wrong KEK or an incorrect MLI.
+    ///
+    /// This method is intended only for debugging, see the security note
+    /// on [`Error::IntegrityCheckFailedDebug`].
+    #[cfg(feature = "debug-errors")]
+    pub fn unwrap_key_debug<'a>(&self, wkey: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        let expected_len = check_unwrap_len(wkey.len())?;
         let buf = buf
             .get_mut(..expected_len)
             .ok_or(Error::InvalidOutputSize { expected_len })?;
 
-        self.unwrap_key_trusted(data, buf)
+        let (iv, body) = wkey.split_at(IV_LEN);
+        buf.copy_from_slice(body);
+        let aiv = self.decrypt_in_place_trusted(iv, buf);
+        check_aiv(&aiv, &KWP_AIV_PREFIX, buf)
+            .map_err(|_| Error::IntegrityCheckFailedDebug { computed: aiv })
+    }
+
+    /// AES Key Wrap with Padding, as defined in RFC 5649.
+    ///
+    /// Same as [`AesKwp::unwrap_key`], but length of `buf` must be exactly equal
+    /// to `data.len() - IV_LEN`, otherwise [`Error::InvalidOutputSize`] is returned.
+    #[inline]
+    pub fn unwrap_key_exact<'a>(&self, data: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        let expected_len = check_unwrap_len(data.len())?;
+        if buf.len() != expected_len {
+            return Err(Error::InvalidOutputSize { expected_len });
+        }
+
+        self.unwrap_key_trusted(data, &KWP_AIV_PREFIX, buf)
             .map_err(|_| Error::IntegrityCheckFailed)
     }
 
+    /// Unwrap `data` into `scratch` and pass the unwrapped key to `f`.
+    ///
+    /// `scratch` is zeroed after `f` returns, so the unwrapped key does not outlive
+    /// the call. `f` is called only if the integrity check passes.
+    ///
+    /// Size requirements for `data` and `scratch` are the same as in [`AesKwp::unwrap_key`].
+    #[inline]
+    pub fn unwrap_key_with<R, F: FnOnce(&[u8]) -> R>(
+        &self,
+        data: &[u8],
+        scratch: &mut [u8],
+        f: F,
+    ) -> Result<R, Error> {
+        let res = self.unwrap_key(data, scratch).map(f);
+        scratch.fill(0);
+        res
+    }
+
+    /// Check integrity of `wkey`, including the AIV and padding,
+    /// without exposing the unwrapped key.
+    ///
+    /// The key is unwrapped into an internal buffer, which gets zeroed
+    /// before returning. Returns [`Error::IntegrityCheckFailed`] if `wkey`
+    /// was not wrapped using this KEK.
+    ///
+    /// Length requirements for `wkey` are the same as in [`AesKwp::unwrap_key`].
+    #[cfg(feature = "alloc")]
+    pub fn verify(&self, wkey: &[u8]) -> Result<(), Error> {
+        let mut scratch = vec![0u8; Self::required_unwrap_len(wkey.len())?];
+        self.unwrap_key_with(wkey, &mut scratch, |_| ())
+    }
+
     /// Unwrap fixed-size wrapped key `wkey` and return resulting key.
     ///
     /// This method is roughly equivalent to:
@@ -255,13 +743,15 @@ impl<C: BlockCipherDecrypt<BlockSize = U16>> AesKwp<C> {
     /// ```
     /// but uses [`hybrid_array::Array`][Array] instead of built-in arrays
     /// to work around current limitations of the const generics system.
+    /// Built-in arrays can be converted to and from [`Array`] using
+    /// the [`From`]/[`Into`] traits.
     #[inline]
     pub fn unwrap_fixed_key<N>(
         &self,
         wkey: &KwpWrappedKey<N>,
     ) -> Result<Array<u8, N>, IntegrityCheckFailed>
     where
-        N: ArraySize + Add<IvLenM1> + IsLess<KwpMaxLen>,
+        N: ArraySize + NonZero + Add<IvLenM1> + IsLess<KwpMaxLen>,
         Le<N, KwpMaxLen>: NonZero,
         Sum<N, IvLenM1>: Div<IvLen>,
         Quot<Sum<N, IvLenM1>, IvLen>: Add<B1> + Mul<IvLen>,
@@ -269,8 +759,164 @@ impl<C: BlockCipherDecrypt<BlockSize = U16>> AesKwp<C> {
         Prod<Add1<Quot<Sum<N, IvLenM1>, IvLen>>, IvLen>: ArraySize,
         Prod<Quot<Sum<N, IvLenM1>, IvLen>, IvLen>: ArraySize,
     {
-        let mut buf = Array::<u8, Prod<Quot<Sum<N, IvLenM1>, IvLen>, IvLen>>::default();
-        self.unwrap_key_trusted(wkey, &mut buf)
-            .map(|res| res.try_into().unwrap())
+        let mut wkey = wkey.clone();
+        let res = self.unwrap_fixed_key_in_place(&mut wkey).cloned();
+        wkey.fill(0);
+        res
+    }
+
+    /// Unwrap key in `wkey` and return unwrapped key stored in a built-in array.
+    ///
+    /// Same as [`AesKwp::unwrap_fixed_key`], but uses built-in arrays instead of [`Array`].
+    /// Lengths are checked at compile time, see [`AesKwp::wrap_array`] for details.
+    #[inline]
+    pub fn unwrap_array<const N: usize, const M: usize>(
+        &self,
+        wkey: &[u8; M],
+    ) -> Result<[u8; N], IntegrityCheckFailed> {
+        const { check_array_lens(N, M) };
+        let mut wkey = *wkey;
+        let (iv, body) = wkey.split_at_mut(IV_LEN);
+        let res = self
+            .unwrap_in_place_trusted(iv, &KWP_AIV_PREFIX, body)
+            // MLI may specify a key length different from `N`
+            .and_then(|key| key.try_into().map_err(|_| IntegrityCheckFailed));
+        wkey.fill(0);
+        res
+    }
+
+    /// Unwrap key in `wkey` and return unwrapped key which gets zeroized on drop.
+    ///
+    /// Same as [`AesKwp::unwrap_fixed_key`], but returns [`SecretKey`].
+    #[cfg(feature = "zeroize")]
+    #[inline]
+    pub fn unwrap_fixed_key_secret<N>(
+        &self,
+        wkey: &KwpWrappedKey<N>,
+    ) -> Result<SecretKey<N>, IntegrityCheckFailed>
+    where
+        N: ArraySize + NonZero + Add<IvLenM1> + IsLess<KwpMaxLen>,
+        Le<N, KwpMaxLen>: NonZero,
+        Sum<N, IvLenM1>: Div<IvLen>,
+        Quot<Sum<N, IvLenM1>, IvLen>: Add<B1> + Mul<IvLen>,
+        Add1<Quot<Sum<N, IvLenM1>, IvLen>>: Mul<IvLen>,
+        Prod<Add1<Quot<Sum<N, IvLenM1>, IvLen>>, IvLen>: ArraySize,
+        Prod<Quot<Sum<N, IvLenM1>, IvLen>, IvLen>: ArraySize,
+    {
+        let mut wkey = wkey.clone();
+        let res = self.unwrap_fixed_key_in_place(&mut wkey).cloned();
+        wkey.fill(0);
+        res.map(SecretKey)
+    }
+
+    /// Unwrap key in `wkey` in place and return reference to the unwrapped key.
+    ///
+    /// Same as [`AesKwp::unwrap_fixed_key`], but does not use an intermediate
+    /// buffer, which reduces stack usage for big keys. The unwrapped key is stored
+    /// in `wkey` right after the first [`IV_LEN`] bytes. On integrity check failure
+    /// the unwrapped data in `wkey` is zeroed.
+    #[inline]
+    pub fn unwrap_fixed_key_in_place<'a, N>(
+        &self,
+        wkey: &'a mut KwpWrappedKey<N>,
+    ) -> Result<&'a Array<u8, N>, IntegrityCheckFailed>
+    where
+        N: ArraySize + NonZero + Add<IvLenM1> + IsLess<KwpMaxLen>,
+        Le<N, KwpMaxLen>: NonZero,
+        Sum<N, IvLenM1>: Div<IvLen>,
+        Quot<Sum<N, IvLenM1>, IvLen>: Add<B1> + Mul<IvLen>,
+        Add1<Quot<Sum<N, IvLenM1>, IvLen>>: Mul<IvLen>,
+        Prod<Add1<Quot<Sum<N, IvLenM1>, IvLen>>, IvLen>: ArraySize,
+        Prod<Quot<Sum<N, IvLenM1>, IvLen>, IvLen>: ArraySize,
+    {
+        let (iv, body) = wkey.split_at_mut(IV_LEN);
+        let key_len = self
+            .unwrap_in_place_trusted(iv, &KWP_AIV_PREFIX, body)?
+            .len();
+        // MLI may specify a key length different from `N`
+        if key_len != N::USIZE {
+            body.fill(0);
+            return Err(IntegrityCheckFailed);
+        }
+        Ok((&body[..key_len]).try_into().unwrap())
+    }
+
+    /// Unwrap `wkey` and write the resulting key to `writer`.
+    ///
+    /// No data is written to `writer` unless the AIV verification passes,
+    /// i.e. on integrity check failure `writer` is left untouched and
+    /// an [`io::ErrorKind::InvalidData`] error is returned. The intermediate
+    /// buffer is zeroed before being dropped.
+    ///
+    /// Returns number of bytes written to `writer`.
+    #[cfg(feature = "std")]
+    pub fn unwrap_key_to_writer<W: Write>(&self, wkey: &[u8], mut writer: W) -> io::Result<usize> {
+        let mut buf = vec![0u8; wkey.len().saturating_sub(IV_LEN)];
+        let res = match self.unwrap_key(wkey, &mut buf) {
+            Ok(key) => writer.write_all(key).map(|()| key.len()),
+            Err(err) => Err(err.into()),
+        };
+        buf.fill(0);
+        res
+    }
+}
+
+impl<C: BlockCipherEncrypt<BlockSize = U16>> KeyWrapEncrypt for AesKwp<C> {
+    fn iv_len(&self) -> usize {
+        IV_LEN / 2
+    }
+
+    fn wrap_len(&self, key_len: usize) -> Result<usize, Error> {
+        Self::required_wrap_len(key_len)
+    }
+
+    fn wrap_key_default<'a>(&self, key: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        self.wrap_key(key, buf)
+    }
+
+    fn wrap_key_with_iv<'a>(
+        &self,
+        key: &[u8],
+        iv: &[u8],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        let aiv_prefix = fixed_iv::<{ IV_LEN / 2 }>(iv)?;
+        let expected_len = Self::required_wrap_len(key.len())?;
+        let buf = buf
+            .get_mut(..expected_len)
+            .ok_or(Error::InvalidOutputSize { expected_len })?;
+
+        self.wrap_key_trusted(key, aiv_prefix, buf);
+        Ok(buf)
+    }
+}
+
+impl<C: BlockCipherDecrypt<BlockSize = U16>> KeyWrapDecrypt for AesKwp<C> {
+    fn iv_len(&self) -> usize {
+        IV_LEN / 2
+    }
+
+    fn unwrap_len(&self, wkey_len: usize) -> Result<usize, Error> {
+        Self::required_unwrap_len(wkey_len)
+    }
+
+    fn unwrap_key_default<'a>(&self, wkey: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        self.unwrap_key(wkey, buf)
+    }
+
+    fn unwrap_key_with_iv<'a>(
+        &self,
+        wkey: &[u8],
+        iv: &[u8],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Error> {
+        let aiv_prefix = fixed_iv::<{ IV_LEN / 2 }>(iv)?;
+        let expected_len = check_unwrap_len(wkey.len())?;
+        let buf = buf
+            .get_mut(..expected_len)
+            .ok_or(Error::InvalidOutputSize { expected_len })?;
+
+        self.unwrap_key_trusted(wkey, aiv_prefix, buf)
+            .map_err(|_| Error::IntegrityCheckFailed)
     }
 }
@@ -5,26 +5,113 @@
     html_favicon_url = "https://raw.githubusercontent.com/RustCrypto/media/6ee8e381/logo.svg"
 )]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
-#![forbid(unsafe_code)]
+// `unsafe` is used only in `AesKw::unwrap_key_uninit`
+#![deny(unsafe_code)]
 #![warn(missing_docs, rust_2018_idioms)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "cms")]
+pub mod cms;
 #[cfg(feature = "oid")]
 mod oid;
 
+mod alg;
+mod backend;
+#[cfg(feature = "alloc")]
+mod bundle;
 mod ctx;
 mod error;
+#[cfg(feature = "magma")]
+mod gost_kw;
+mod hex;
+mod jwe;
 mod kw;
+mod kw_cipher;
 mod kwp;
+#[cfg(feature = "zeroize")]
+mod secret;
+#[cfg(feature = "alloc")]
+mod shared;
+#[cfg(feature = "des")]
+mod tkw;
+mod traits;
+#[cfg(feature = "alloc")]
+mod wrapped;
 
 use aes::cipher::consts::U8;
 use aes::cipher::typenum::Unsigned;
-pub use error::{Error, IntegrityCheckFailed};
-pub use kw::AesKw;
-pub use kwp::AesKwp;
+pub use alg::KeyWrapAlgorithm;
+pub use backend::{unwrap_with_backend, wrap_with_backend};
+#[cfg(feature = "alloc")]
+pub use bundle::KeyBundle;
+pub use error::{BatchError, Error, IntegrityCheckFailed};
+#[cfg(feature = "magma")]
+pub use gost_kw::{CryptoProA, MagmaKeyWrap, Tc26};
+pub use hex::HexWrapped;
+pub use jwe::JweAlg;
+pub use kw::{AesKw, AesKwRef, KwWrappedKey, AES_KW_IV};
+pub use kw_cipher::KwCipher;
+pub use kwp::{AesKwp, AesKwpRef, KwpWrappedKey, KWP_AIV_PREFIX};
+#[cfg(feature = "oid")]
+pub use oid::{kek_len_for_oid, key_wrap_for_oid};
+#[cfg(feature = "zeroize")]
+pub use secret::SecretKey;
+#[cfg(feature = "alloc")]
+pub use shared::SharedAesKw;
+#[cfg(feature = "des")]
+pub use tkw::{Tkw, TKW_IV};
+pub use traits::{KeyWrap, KeyWrapDecrypt, KeyWrapEncrypt};
+#[cfg(feature = "alloc")]
+pub use wrapped::WrappedKey;
 
 pub use aes;
 pub use aes::cipher;
-pub use aes::cipher::{crypto_common::InnerInit, KeyInit};
+pub use aes::cipher::{
+    crypto_common::{BlockSizeUser, InnerInit},
+    KeyInit,
+};
+#[cfg(feature = "belt-kwp")]
+pub use belt_kwp;
+#[cfg(feature = "bytes")]
+pub use bytes;
+#[cfg(feature = "oid")]
+pub use const_oid;
+#[cfg(feature = "des")]
+pub use des;
+#[cfg(feature = "getrandom")]
+pub use getrandom;
+#[cfg(feature = "heapless")]
+pub use heapless;
+pub use key_wrap_traits;
+#[cfg(feature = "kuznyechik")]
+pub use kuznyechik;
+#[cfg(feature = "magma")]
+pub use magma;
+#[cfg(feature = "rand_core")]
+pub use rand_core;
+#[cfg(feature = "rayon")]
+pub use rayon;
+#[cfg(feature = "secrecy")]
+pub use secrecy;
+#[cfg(feature = "serde")]
+pub use serde;
+#[cfg(feature = "subtle")]
+pub use subtle;
+#[cfg(feature = "zeroize")]
+pub use zeroize;
+
+/// Key wrapping (KW) over a generic block cipher with 128-bit block size.
+///
+/// This is an alias for [`AesKw`], which despite the name is not AES-specific.
+pub type BlockCipherKw<C> = AesKw<C>;
+/// Key wrapping with padding (KWP) over a generic block cipher with 128-bit block size.
+///
+/// This is an alias for [`AesKwp`], which despite the name is not AES-specific.
+pub type BlockCipherKwp<C> = AesKwp<C>;
 
 /// AES-128 key wrapping
 pub type KwAes128 = AesKw<aes::Aes128>;
@@ -40,6 +127,71 @@ pub type KwpAes192 = AesKwp<aes::Aes192>;
 /// AES-256 key wrapping
 pub type KwpAes256 = AesKwp<aes::Aes256>;
 
+/// AES-128 key wrapping which supports only wrapping
+pub type KwAes128Enc = AesKw<aes::Aes128Enc>;
+/// AES-192 key wrapping which supports only wrapping
+pub type KwAes192Enc = AesKw<aes::Aes192Enc>;
+/// AES-256 key wrapping which supports only wrapping
+pub type KwAes256Enc = AesKw<aes::Aes256Enc>;
+
+/// AES-128 key wrapping which supports only unwrapping
+pub type KwAes128Dec = AesKw<aes::Aes128Dec>;
+/// AES-192 key wrapping which supports only unwrapping
+pub type KwAes192Dec = AesKw<aes::Aes192Dec>;
+/// AES-256 key wrapping which supports only unwrapping
+pub type KwAes256Dec = AesKw<aes::Aes256Dec>;
+
+/// AES-128 key wrapping with padding which supports only wrapping
+pub type KwpAes128Enc = AesKwp<aes::Aes128Enc>;
+/// AES-192 key wrapping with padding which supports only wrapping
+pub type KwpAes192Enc = AesKwp<aes::Aes192Enc>;
+/// AES-256 key wrapping with padding which supports only wrapping
+pub type KwpAes256Enc = AesKwp<aes::Aes256Enc>;
+
+/// AES-128 key wrapping with padding which supports only unwrapping
+pub type KwpAes128Dec = AesKwp<aes::Aes128Dec>;
+/// AES-192 key wrapping with padding which supports only unwrapping
+pub type KwpAes192Dec = AesKwp<aes::Aes192Dec>;
+/// AES-256 key wrapping with padding which supports only unwrapping
+pub type KwpAes256Dec = AesKwp<aes::Aes256Dec>;
+
+macro_rules! impl_from_key {
+    ($($ty:ty => $n:literal,)*) => {$(
+        impl From<[u8; $n]> for $ty {
+            #[inline]
+            fn from(key: [u8; $n]) -> Self {
+                Self::new(&key.into())
+            }
+        }
+    )*};
+}
+
+impl_from_key!(
+    KwAes128 => 16,
+    KwAes192 => 24,
+    KwAes256 => 32,
+    KwpAes128 => 16,
+    KwpAes192 => 24,
+    KwpAes256 => 32,
+);
+
+/// Kuznyechik key wrapping
+#[cfg(feature = "kuznyechik")]
+pub type KwKuznyechik = AesKw<kuznyechik::Kuznyechik>;
+/// Kuznyechik key wrapping with padding
+#[cfg(feature = "kuznyechik")]
+pub type KwpKuznyechik = AesKwp<kuznyechik::Kuznyechik>;
+
+/// Compare two wrapped keys in constant time.
+///
+/// Only contents of `a` and `b` are compared in constant time, while
+/// their lengths are not considered secret.
+#[cfg(feature = "subtle")]
+#[inline]
+pub fn ct_eq(a: &[u8], b: &[u8]) -> subtle::Choice {
+    subtle::ConstantTimeEq::ct_eq(a, b)
+}
+
 /// Size of an AES-KW and AES-KWP initialization vector in bytes represented as a `typenum` type.
 pub type IvLen = U8;
 /// Size of an AES-KW and AES-KWP initialization vector in bytes.
@@ -48,3 +200,28 @@ pub type IvLen = U8;
 ///
 /// > semiblock: given a block cipher, a bit string whose length is half of the block size.
 pub const IV_LEN: usize = IvLen::USIZE;
+
+/// Iterate over semiblocks of `data`.
+///
+/// `data` can be either a wrapped or a plaintext key. Returns
+/// [`Error::NotSemiblockAligned`] if length of `data` is not multiple of [`IV_LEN`].
+///
+/// # Example
+/// ```
+/// use aes_kw::semiblocks;
+///
+/// let wkey = [0xA6; 24];
+/// let mut iter = semiblocks(&wkey).unwrap();
+/// assert_eq!(iter.next(), Some(&[0xA6; 8]));
+/// assert_eq!(iter.count(), 2);
+/// assert!(semiblocks(&wkey[..20]).is_err());
+/// ```
+#[inline]
+pub fn semiblocks(data: &[u8]) -> Result<impl Iterator<Item = &[u8; IV_LEN]>, Error> {
+    if data.len() % IV_LEN != 0 {
+        return Err(Error::NotSemiblockAligned);
+    }
+    Ok(data
+        .chunks_exact(IV_LEN)
+        .map(|chunk| chunk.try_into().unwrap()))
+}
@@ -1,4 +1,5 @@
 //! OIDs from RFC 3394 and RFC 5649
+use crate::KeyWrapAlgorithm;
 use const_oid::{AssociatedOid, ObjectIdentifier};
 
 impl AssociatedOid for super::KwAes128 {
@@ -24,3 +25,52 @@ impl AssociatedOid for super::KwpAes192 {
 impl AssociatedOid for super::KwpAes256 {
     const OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.1.48");
 }
+
+/// All algorithms with an associated OID.
+const ALGORITHMS: &[KeyWrapAlgorithm] = &[
+    KeyWrapAlgorithm::KwAes128,
+    KeyWrapAlgorithm::KwAes192,
+    KeyWrapAlgorithm::KwAes256,
+    KeyWrapAlgorithm::KwpAes128,
+    KeyWrapAlgorithm::KwpAes192,
+    KeyWrapAlgorithm::KwpAes256,
+];
+
+impl KeyWrapAlgorithm {
+    /// Object identifier of the algorithm.
+    pub const fn oid(self) -> ObjectIdentifier {
+        match self {
+            Self::KwAes128 => super::KwAes128::OID,
+            Self::KwAes192 => super::KwAes192::OID,
+            Self::KwAes256 => super::KwAes256::OID,
+            Self::KwpAes128 => super::KwpAes128::OID,
+            Self::KwpAes192 => super::KwpAes192::OID,
+            Self::KwpAes256 => super::KwpAes256::OID,
+        }
+    }
+}
+
+impl KeyWrapAlgorithm {
+    /// Find algorithm identified by `oid`.
+    ///
+    /// Same as [`key_wrap_for_oid`].
+    #[inline]
+    pub fn from_oid(oid: &ObjectIdentifier) -> Option<Self> {
+        key_wrap_for_oid(oid)
+    }
+}
+
+/// Find key wrap algorithm identified by `oid`.
+///
+/// Returns `None` if `oid` does not correspond to any of the supported algorithms.
+pub fn key_wrap_for_oid(oid: &ObjectIdentifier) -> Option<KeyWrapAlgorithm> {
+    ALGORITHMS.iter().copied().find(|alg| alg.oid() == *oid)
+}
+
+/// Get length of the key-encryption key in bytes for key wrap algorithm identified by `oid`.
+///
+/// This is useful for validating a KEK before constructing the wrapper.
+/// Returns `None` if `oid` does not correspond to any of the supported algorithms.
+pub fn kek_len_for_oid(oid: &ObjectIdentifier) -> Option<usize> {
+    key_wrap_for_oid(oid).map(KeyWrapAlgorithm::kek_len)
+}
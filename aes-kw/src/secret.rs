@@ -0,0 +1,49 @@
+//! Zeroizing container for unwrapped keys
+use aes::cipher::{array::ArraySize, Array};
+use core::{fmt, ops::Deref};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Unwrapped key of `N` bytes which gets zeroized on drop.
+///
+/// Returned by [`AesKw::unwrap_fixed_key_secret`][crate::AesKw::unwrap_fixed_key_secret]
+/// and [`AesKwp::unwrap_fixed_key_secret`][crate::AesKwp::unwrap_fixed_key_secret].
+/// Key bytes can be accessed using [`Deref`].
+pub struct SecretKey<N: ArraySize>(pub(crate) Array<u8, N>);
+
+impl<N: ArraySize> Deref for SecretKey<N> {
+    type Target = Array<u8, N>;
+
+    #[inline]
+    fn deref(&self) -> &Array<u8, N> {
+        &self.0
+    }
+}
+
+impl<N: ArraySize> AsRef<[u8]> for SecretKey<N> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl<N: ArraySize> fmt::Debug for SecretKey<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey { ... }")
+    }
+}
+
+impl<N: ArraySize> Zeroize for SecretKey<N> {
+    #[inline]
+    fn zeroize(&mut self) {
+        self.0.as_mut_slice().zeroize();
+    }
+}
+
+impl<N: ArraySize> Drop for SecretKey<N> {
+    #[inline]
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl<N: ArraySize> ZeroizeOnDrop for SecretKey<N> {}
@@ -0,0 +1,69 @@
+//! Shared key wrapper
+use crate::{AesKw, KwCipher};
+use aes::cipher::crypto_common::{InnerInit, InnerUser};
+use alloc::sync::Arc;
+use core::{fmt, ops::Deref};
+
+/// [`AesKw`] instance shared using [`Arc`].
+///
+/// Cloning is cheap and does not require `C: Clone`, which allows one KEK
+/// cipher to be shared across threads. All wrap and unwrap methods of [`AesKw`]
+/// are available through [`Deref`].
+///
+/// It can be initialized using [`KeyInit`][aes::cipher::KeyInit] or created
+/// from an existing [`AesKw`] using the [`From`] trait.
+pub struct SharedAesKw<C: KwCipher>(Arc<AesKw<C>>);
+
+impl<C: KwCipher> Clone for SharedAesKw<C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<C: KwCipher> fmt::Debug for SharedAesKw<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedAesKw { ... }")
+    }
+}
+
+impl<C: KwCipher> Deref for SharedAesKw<C> {
+    type Target = AesKw<C>;
+
+    #[inline]
+    fn deref(&self) -> &AesKw<C> {
+        &self.0
+    }
+}
+
+impl<C: KwCipher> AsRef<AesKw<C>> for SharedAesKw<C> {
+    #[inline]
+    fn as_ref(&self) -> &AesKw<C> {
+        &self.0
+    }
+}
+
+impl<C: KwCipher> From<AesKw<C>> for SharedAesKw<C> {
+    #[inline]
+    fn from(kw: AesKw<C>) -> Self {
+        Self(Arc::new(kw))
+    }
+}
+
+impl<C: KwCipher> From<Arc<AesKw<C>>> for SharedAesKw<C> {
+    #[inline]
+    fn from(kw: Arc<AesKw<C>>) -> Self {
+        Self(kw)
+    }
+}
+
+impl<C: KwCipher> InnerUser for SharedAesKw<C> {
+    type Inner = AesKw<C>;
+}
+
+impl<C: KwCipher> InnerInit for SharedAesKw<C> {
+    #[inline]
+    fn inner_init(kw: Self::Inner) -> Self {
+        Self(Arc::new(kw))
+    }
+}
@@ -0,0 +1,212 @@
+//! NIST SP 800-38F TDEA key wrapping (TKW)
+use crate::Error;
+use aes::cipher::{
+    crypto_common::{InnerInit, InnerUser},
+    Block, BlockCipherDecrypt, BlockCipherEncrypt,
+};
+use core::fmt;
+use des::TdesEde3;
+
+/// Size of the TKW semiblock and integrity register in bytes.
+const SEMIBLOCK_LEN: usize = 4;
+/// Maximum number of semiblocks in the key (SP 800-38F § 6.3).
+const MAX_SEMIBLOCKS: usize = (1 << 28) - 1;
+
+/// Integrity Check Value `ICV3` for TKW as defined in NIST SP 800-38F § 6.3.
+pub const TKW_IV: [u8; SEMIBLOCK_LEN] = [0xA6, 0xA6, 0xA6, 0xA6];
+
+/// TDEA Key Wrapper (TKW), as defined in [NIST SP 800-38F] § 6.3.
+///
+/// TKW is the `W` function applied to the 64-bit block TDEA, so semiblocks
+/// and the integrity register are 32 bits long and wrapping adds 4 bytes to the key.
+/// Note that this construction is different from the Triple-DES key wrap
+/// defined in RFC 3217.
+///
+/// [NIST SP 800-38F]: https://doi.org/10.6028/NIST.SP.800-38F
+#[derive(Clone)]
+pub struct Tkw {
+    cipher: TdesEde3,
+}
+
+impl fmt::Debug for Tkw {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Tkw { ... }")
+    }
+}
+
+impl InnerUser for Tkw {
+    type Inner = TdesEde3;
+}
+
+impl InnerInit for Tkw {
+    #[inline]
+    fn inner_init(cipher: TdesEde3) -> Self {
+        Self { cipher }
+    }
+}
+
+/// Check lengths of the key and output buffer for wrapping.
+///
+/// Returns length of the wrapped key.
+fn check_wrap_len(key_len: usize, buf_len: usize) -> Result<usize, Error> {
+    let semiblocks = key_len / SEMIBLOCK_LEN;
+    if key_len % SEMIBLOCK_LEN != 0 || !(2..=MAX_SEMIBLOCKS).contains(&semiblocks) {
+        return Err(Error::InvalidDataSize);
+    }
+
+    let expected_len = key_len + SEMIBLOCK_LEN;
+    if buf_len < expected_len {
+        return Err(Error::InvalidOutputSize { expected_len });
+    }
+    Ok(expected_len)
+}
+
+/// Check lengths of the wrapped key and output buffer for unwrapping.
+///
+/// Returns length of the unwrapped key.
+fn check_unwrap_len(wkey_len: usize, buf_len: usize) -> Result<usize, Error> {
+    if wkey_len % SEMIBLOCK_LEN != 0 {
+        return Err(Error::NotSemiblockAligned);
+    }
+    if wkey_len < 3 * SEMIBLOCK_LEN {
+        return Err(Error::TooShort {
+            min: 3 * SEMIBLOCK_LEN,
+        });
+    }
+    if wkey_len / SEMIBLOCK_LEN - 1 > MAX_SEMIBLOCKS {
+        return Err(Error::InvalidDataSize);
+    }
+
+    let expected_len = wkey_len - SEMIBLOCK_LEN;
+    if buf_len < expected_len {
+        return Err(Error::InvalidOutputSize { expected_len });
+    }
+    Ok(expected_len)
+}
+
+/// Compute the counter `t = n * j + i` for the 1-based semiblock index `i`.
+///
+/// The number of semiblocks is limited to `2^28 - 1`, so `t` always fits into `u32`.
+#[inline(always)]
+fn counter(blocks_len: usize, j: usize, i: usize) -> u32 {
+    (blocks_len * j + i) as u32
+}
+
+impl Tkw {
+    /// Length of a semiblock in bytes, i.e. half of the TDEA block.
+    pub const SEMIBLOCK_LEN: usize = SEMIBLOCK_LEN;
+    /// Number of bytes added to a key by wrapping.
+    pub const OVERHEAD: usize = SEMIBLOCK_LEN;
+    /// Minimum supported length of a key in bytes.
+    pub const MIN_KEY_LEN: usize = 2 * SEMIBLOCK_LEN;
+    /// Maximum supported length of a key in bytes.
+    ///
+    /// Keys must also have length multiple of [`Tkw::SEMIBLOCK_LEN`].
+    pub const MAX_KEY_LEN: usize = MAX_SEMIBLOCKS * SEMIBLOCK_LEN;
+
+    /// Get length of the wrapped key for a key of length `key_len`.
+    ///
+    /// Returns [`Error::InvalidDataSize`] if `key_len` is not supported.
+    #[inline]
+    pub fn required_wrap_len(key_len: usize) -> Result<usize, Error> {
+        check_wrap_len(key_len, usize::MAX)
+    }
+
+    /// Get length of the unwrapped key for a wrapped key of length `wkey_len`.
+    ///
+    /// Returns [`Error::NotSemiblockAligned`], [`Error::TooShort`], or
+    /// [`Error::InvalidDataSize`] if `wkey_len` is not supported.
+    #[inline]
+    pub fn required_unwrap_len(wkey_len: usize) -> Result<usize, Error> {
+        check_unwrap_len(wkey_len, usize::MAX)
+    }
+
+    /// Get reference to the inner block cipher.
+    #[inline]
+    pub fn get_inner(&self) -> &TdesEde3 {
+        &self.cipher
+    }
+
+    /// Wrap `key` and write result to `buf`.
+    ///
+    /// Returns slice which points to `buf` and contains wrapped data.
+    ///
+    /// Length of `key` must be multiple of [`Tkw::SEMIBLOCK_LEN`] and
+    /// bigger or equal to [`Tkw::MIN_KEY_LEN`].
+    /// Length of `buf` must be bigger or equal to `key.len() + Tkw::SEMIBLOCK_LEN`.
+    pub fn wrap_key<'a>(&self, key: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        let expected_len = check_wrap_len(key.len(), buf.len())?;
+        let buf = &mut buf[..expected_len];
+        let (a, body) = buf.split_at_mut(SEMIBLOCK_LEN);
+        body.copy_from_slice(key);
+
+        let blocks_len = body.len() / SEMIBLOCK_LEN;
+        let block = &mut Block::<TdesEde3>::default();
+        block[..SEMIBLOCK_LEN].copy_from_slice(&TKW_IV);
+
+        for j in 0..6 {
+            for (i, chunk) in body.chunks_exact_mut(SEMIBLOCK_LEN).enumerate() {
+                // B = CIPH(K, A | R[i])
+                block[SEMIBLOCK_LEN..].copy_from_slice(chunk);
+                self.cipher.encrypt_block(block);
+
+                // A = MSB(32, B) ^ t
+                let t = counter(blocks_len, j, i + 1);
+                xor_counter(block, t);
+                // R[i] = LSB(32, B)
+                chunk.copy_from_slice(&block[SEMIBLOCK_LEN..]);
+            }
+        }
+
+        a.copy_from_slice(&block[..SEMIBLOCK_LEN]);
+        Ok(buf)
+    }
+
+    /// Unwrap `wkey` and write result to `buf`.
+    ///
+    /// Returns slice which points to `buf` and contains unwrapped data.
+    /// On integrity check failure `buf` is zeroed.
+    ///
+    /// Length of `wkey` must be multiple of [`Tkw::SEMIBLOCK_LEN`] and bigger or
+    /// equal to `3 * Tkw::SEMIBLOCK_LEN`.
+    /// Length of `buf` must be bigger or equal to `wkey.len() - Tkw::SEMIBLOCK_LEN`.
+    pub fn unwrap_key<'a>(&self, wkey: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error> {
+        let expected_len = check_unwrap_len(wkey.len(), buf.len())?;
+        let buf = &mut buf[..expected_len];
+        let (a, body) = wkey.split_at(SEMIBLOCK_LEN);
+        buf.copy_from_slice(body);
+
+        let blocks_len = buf.len() / SEMIBLOCK_LEN;
+        let block = &mut Block::<TdesEde3>::default();
+        block[..SEMIBLOCK_LEN].copy_from_slice(a);
+
+        for j in (0..6).rev() {
+            for (i, chunk) in buf.chunks_exact_mut(SEMIBLOCK_LEN).enumerate().rev() {
+                // B = CIPH^-1(K, (A ^ t) | R[i])
+                let t = counter(blocks_len, j, i + 1);
+                xor_counter(block, t);
+                block[SEMIBLOCK_LEN..].copy_from_slice(chunk);
+                self.cipher.decrypt_block(block);
+
+                // A = MSB(32, B), R[i] = LSB(32, B)
+                chunk.copy_from_slice(&block[SEMIBLOCK_LEN..]);
+            }
+        }
+
+        let expected_iv = u32::from_ne_bytes(TKW_IV);
+        let calc_iv = u32::from_ne_bytes(block[..SEMIBLOCK_LEN].try_into().unwrap());
+        if calc_iv == expected_iv {
+            Ok(buf)
+        } else {
+            buf.fill(0);
+            Err(Error::IntegrityCheckFailed)
+        }
+    }
+}
+
+/// XOR big-endian representation of the counter `t` into the integrity register `A`.
+#[inline(always)]
+fn xor_counter(block: &mut Block<TdesEde3>, t: u32) {
+    let a: &mut [u8; SEMIBLOCK_LEN] = (&mut block[..SEMIBLOCK_LEN]).try_into().unwrap();
+    *a = (u32::from_be_bytes(*a) ^ t).to_be_bytes();
+}
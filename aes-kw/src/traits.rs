@@ -0,0 +1,10 @@
+//! Object-safe key wrapping traits
+use crate::Error;
+
+pub use key_wrap_traits::{KeyWrap, KeyWrapDecrypt, KeyWrapEncrypt};
+
+/// Get fixed-size IV from `iv` or return [`Error::InvalidIvSize`].
+pub(crate) fn fixed_iv<const N: usize>(iv: &[u8]) -> Result<&[u8; N], Error> {
+    iv.try_into()
+        .map_err(|_| Error::InvalidIvSize { expected_len: N })
+}
@@ -0,0 +1,144 @@
+//! Owned wrapped key
+use crate::{Error, IV_LEN};
+use alloc::vec::Vec;
+use core::ops::Deref;
+
+#[cfg(feature = "serde")]
+use base64ct::{Base64UrlUnpadded, Encoding};
+#[cfg(feature = "serde")]
+use core::fmt;
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+/// Owned wrapped key produced by AES-KW or AES-KWP.
+///
+/// Length of the wrapped key is guaranteed to be a positive multiple of [`IV_LEN`].
+/// Wrapped key bytes can be accessed using [`Deref`].
+///
+/// With the `serde` feature enabled the wrapped key is serialized as bytes
+/// in binary formats and as unpadded base64url string in human-readable ones.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WrappedKey(Vec<u8>);
+
+impl WrappedKey {
+    /// Create new wrapped key from `bytes`.
+    ///
+    /// Returns [`Error::InvalidDataSize`] if length of `bytes` is not
+    /// a positive multiple of [`IV_LEN`].
+    pub fn new(bytes: Vec<u8>) -> Result<Self, Error> {
+        if bytes.is_empty() || bytes.len() % IV_LEN != 0 {
+            return Err(Error::InvalidDataSize);
+        }
+        Ok(Self(bytes))
+    }
+
+    /// Get wrapped key bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Convert into a vector of wrapped key bytes.
+    #[inline]
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl Deref for WrappedKey {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for WrappedKey {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl TryFrom<Vec<u8>> for WrappedKey {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(bytes: Vec<u8>) -> Result<Self, Error> {
+        Self::new(bytes)
+    }
+}
+
+impl TryFrom<&[u8]> for WrappedKey {
+    type Error = Error;
+
+    #[inline]
+    fn try_from(bytes: &[u8]) -> Result<Self, Error> {
+        Self::new(bytes.into())
+    }
+}
+
+impl From<WrappedKey> for Vec<u8> {
+    #[inline]
+    fn from(wkey: WrappedKey) -> Vec<u8> {
+        wkey.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for WrappedKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&Base64UrlUnpadded::encode_string(&self.0))
+        } else {
+            serializer.serialize_bytes(&self.0)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for WrappedKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct WrappedKeyVisitor;
+
+        impl<'de> de::Visitor<'de> for WrappedKeyVisitor {
+            type Value = WrappedKey;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("wrapped key with length multiple of 8 bytes")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<WrappedKey, E> {
+                let bytes = Base64UrlUnpadded::decode_vec(v)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))?;
+                let len = bytes.len();
+                WrappedKey::new(bytes).map_err(|_| E::invalid_length(len, &self))
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<WrappedKey, E> {
+                WrappedKey::try_from(v).map_err(|_| E::invalid_length(v.len(), &self))
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<WrappedKey, E> {
+                let len = v.len();
+                WrappedKey::new(v).map_err(|_| E::invalid_length(len, &self))
+            }
+
+            fn visit_seq<A: de::SeqAccess<'de>>(self, mut seq: A) -> Result<WrappedKey, A::Error> {
+                let mut bytes = Vec::new();
+                while let Some(b) = seq.next_element()? {
+                    bytes.push(b);
+                }
+                let len = bytes.len();
+                WrappedKey::new(bytes).map_err(|_| de::Error::invalid_length(len, &self))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(WrappedKeyVisitor)
+        } else {
+            deserializer.deserialize_bytes(WrappedKeyVisitor)
+        }
+    }
+}
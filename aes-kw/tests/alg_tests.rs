@@ -0,0 +1,45 @@
+#![cfg(feature = "alloc")]
+
+use aes_kw::{Error, KeyWrapAlgorithm};
+use hex_literal::hex;
+
+#[test]
+fn wrap_unwrap_kw() {
+    let kek = hex!("000102030405060708090A0B0C0D0E0F1011121314151617");
+    let key = hex!("00112233445566778899AABBCCDDEEFF0001020304050607");
+    let wkey = hex!("031D33264E15D33268F24EC260743EDCE1C6C7DDEE725A936BA814915C6762D2");
+    let alg = KeyWrapAlgorithm::KwAes192;
+
+    assert_eq!(alg.wrap(&kek, &key).unwrap(), wkey);
+    assert_eq!(alg.unwrap(&kek, &wkey).unwrap(), key);
+}
+
+#[test]
+fn wrap_unwrap_kwp() {
+    let kek = hex!("5840DF6E29B02AF1AB493B705BF16EA1AE8338F4DCC176A8");
+    let key = hex!("C37B7E6492584340BED12207808941155068F738");
+    let wkey = hex!("138BDEAA9B8FA7FC61F97742E72248EE5AE6AE5360D1AE6A5F54F373FA543B6A");
+    let alg = KeyWrapAlgorithm::KwpAes192;
+
+    assert_eq!(alg.wrap(&kek, &key).unwrap(), wkey);
+    assert_eq!(alg.unwrap(&kek, &wkey).unwrap(), key);
+}
+
+#[test]
+fn errors() {
+    let kek = hex!("000102030405060708090A0B0C0D0E0F");
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+
+    let res = KeyWrapAlgorithm::KwAes256.wrap(&kek, &[0u8; 16]);
+    assert_eq!(res, Err(Error::InvalidKekSize { bits: 128 }));
+    let res = KeyWrapAlgorithm::KwAes256.unwrap(&kek, &wkey);
+    assert_eq!(res, Err(Error::InvalidKekSize { bits: 128 }));
+
+    let res = KeyWrapAlgorithm::KwAes128.wrap(&kek, &[0u8; 15]);
+    assert_eq!(res, Err(Error::InvalidDataSize));
+
+    let mut bad_wkey = wkey;
+    bad_wkey[0] ^= 1;
+    let res = KeyWrapAlgorithm::KwAes128.unwrap(&kek, &bad_wkey);
+    assert_eq!(res, Err(Error::IntegrityCheckFailed));
+}
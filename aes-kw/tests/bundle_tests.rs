@@ -0,0 +1,42 @@
+#![cfg(feature = "alloc")]
+
+use aes_kw::{Error, KeyBundle, KeyInit, KwAes128};
+use hex_literal::hex;
+
+#[test]
+fn push_parse() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let mut buf1 = [0u8; 24];
+    let mut buf2 = [0u8; 32];
+    let wkey1 = kw
+        .wrap_key(&hex!("00112233445566778899AABBCCDDEEFF"), &mut buf1)
+        .unwrap();
+    let wkey2 = kw.wrap_key(&[0x42; 24], &mut buf2).unwrap();
+
+    let mut bundle = KeyBundle::new();
+    bundle.push(wkey1).unwrap();
+    bundle.push(wkey2).unwrap();
+    let bytes = bundle.into_vec();
+    assert_eq!(bytes.len(), 4 + 24 + 4 + 32);
+    assert_eq!(bytes[..4], [0, 0, 0, 24]);
+
+    let entries = KeyBundle::parse(&bytes).unwrap();
+    assert_eq!(entries, [wkey1, wkey2]);
+
+    let mut buf = [0u8; 16];
+    let res = kw.unwrap_key(entries[0], &mut buf).unwrap();
+    assert_eq!(res, hex!("00112233445566778899AABBCCDDEEFF"));
+
+    assert_eq!(KeyBundle::parse(&[]), Ok(Vec::new()));
+}
+
+#[test]
+fn parse_truncated() {
+    let mut bundle = KeyBundle::new();
+    bundle.push(&[0x42; 24]).unwrap();
+    let bytes = bundle.as_bytes();
+
+    for len in [2, 4, 27] {
+        assert_eq!(KeyBundle::parse(&bytes[..len]), Err(Error::InvalidDataSize));
+    }
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "cms")]
+
+use aes_kw::{
+    cms::{unwrap_cek, wrap_cek},
+    const_oid::ObjectIdentifier,
+    Error,
+};
+use hex_literal::hex;
+
+const ID_AES128_WRAP: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.1.5");
+const ID_AES192_WRAP_PAD: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.1.28");
+const ID_ALG_CMS3DES_WRAP: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.16.3.6");
+
+#[test]
+fn wrap_unwrap_kw() {
+    let kek = hex!("000102030405060708090A0B0C0D0E0F");
+    let cek = hex!("00112233445566778899AABBCCDDEEFF");
+    let wcek = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+
+    assert_eq!(wrap_cek(&ID_AES128_WRAP, &kek, &cek).unwrap(), wcek);
+    assert_eq!(unwrap_cek(&ID_AES128_WRAP, &kek, &wcek).unwrap(), cek);
+}
+
+#[test]
+fn wrap_unwrap_kwp() {
+    let kek = hex!("5840DF6E29B02AF1AB493B705BF16EA1AE8338F4DCC176A8");
+    let cek = hex!("466F7250617369");
+    let wcek = hex!("AFBEB0F07DFBF5419200F2CCB50BB24F");
+
+    assert_eq!(wrap_cek(&ID_AES192_WRAP_PAD, &kek, &cek).unwrap(), wcek);
+    assert_eq!(unwrap_cek(&ID_AES192_WRAP_PAD, &kek, &wcek).unwrap(), cek);
+}
+
+#[test]
+fn errors() {
+    let kek = hex!("000102030405060708090A0B0C0D0E0F");
+
+    let res = wrap_cek(&ID_ALG_CMS3DES_WRAP, &kek, &[0u8; 16]);
+    assert_eq!(res, Err(Error::UnsupportedAlgorithm));
+    let res = unwrap_cek(&ID_ALG_CMS3DES_WRAP, &kek, &[0u8; 24]);
+    assert_eq!(res, Err(Error::UnsupportedAlgorithm));
+
+    // RFC 3394 requires at least two semiblocks of key data
+    let res = wrap_cek(&ID_AES128_WRAP, &kek, &[0u8; 8]);
+    assert_eq!(res, Err(Error::InvalidDataSize));
+    let wcek = aes_kw::KeyWrapAlgorithm::KwAes128
+        .wrap(&kek, &[0u8; 8])
+        .unwrap();
+    let res = unwrap_cek(&ID_AES128_WRAP, &kek, &wcek);
+    assert_eq!(res, Err(Error::InvalidDataSize));
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "kuznyechik")]
+
+use aes_kw::{
+    cipher::consts::{U20, U32, U7},
+    Error, KeyInit, KwKuznyechik, KwpKuznyechik,
+};
+use hex_literal::hex;
+
+macro_rules! test_kuznyechik_kw {
+    ($name:ident, $kw_ty:ty, $n:ty, $key:expr, $pt:expr, $ct:expr) => {
+        #[test]
+        fn $name() {
+            let kw = <$kw_ty>::new(&$key.into());
+            let mut buf = [0u8; 64];
+            let ct = kw.wrap_key(&$pt, &mut buf).unwrap();
+            assert_eq!($ct, ct);
+            let pt = kw.unwrap_key(&$ct, &mut buf).unwrap();
+            assert_eq!($pt, pt);
+
+            let ct = kw.wrap_fixed_key::<$n>((&$pt).try_into().unwrap());
+            assert_eq!($ct, ct);
+            let pt = kw.unwrap_fixed_key::<$n>(&ct).unwrap();
+            assert_eq!($pt, pt);
+        }
+    };
+}
+
+// These vectors were generated by applying the RFC 3394 and RFC 5649
+// constructions to Kuznyechik. The KEK is the sample key from
+// GOST R 34.12-2015 Appendix A.1.
+
+test_kuznyechik_kw!(
+    wrap_unwrap_256_key,
+    KwKuznyechik,
+    U32,
+    hex!("8899AABBCCDDEEFF0011223344556677FEDCBA98765432100123456789ABCDEF"),
+    hex!("00112233445566778899AABBCCDDEEFF000102030405060708090A0B0C0D0E0F"),
+    hex!("399B63B099B678F0D9F732AEF3F0054794DF714ADAFFFC4626CA903FFA557839C85EFDAC05208362")
+);
+test_kuznyechik_kw!(
+    wrap_unwrap_56_key_padded,
+    KwpKuznyechik,
+    U7,
+    hex!("8899AABBCCDDEEFF0011223344556677FEDCBA98765432100123456789ABCDEF"),
+    hex!("466F7250617369"),
+    hex!("4F79871CCAB38203F4D9A35AE828D441")
+);
+test_kuznyechik_kw!(
+    wrap_unwrap_160_key_padded,
+    KwpKuznyechik,
+    U20,
+    hex!("8899AABBCCDDEEFF0011223344556677FEDCBA98765432100123456789ABCDEF"),
+    hex!("C37B7E6492584340BED12207808941155068F738"),
+    hex!("461BD09053EB3EF41E4602A4260FFF1915677BCDFFBB757B945BF908C2E751C1")
+);
+
+#[test]
+fn error_integrity_check_failed() {
+    let key = hex!("8899AABBCCDDEEFF0011223344556677FEDCBA98765432100123456789ABCDEF");
+    let output = hex!("4F79871CCAB38203F4D9A35AE828D442");
+
+    let kwp = KwpKuznyechik::new(&key.into());
+
+    let mut buf = [0u8; 8];
+    let res = kwp.unwrap_key(&output, &mut buf);
+    assert_eq!(res, Err(Error::IntegrityCheckFailed));
+}
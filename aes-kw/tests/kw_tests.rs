@@ -1,6 +1,7 @@
 use aes_kw::{
-    cipher::consts::{U16, U24, U32},
-    Error, KeyInit, KwAes128, KwAes192, KwAes256,
+    cipher::consts::{U16, U24, U32, U8},
+    BatchError, BlockSizeUser, Error, HexWrapped, IntegrityCheckFailed, KeyInit, KwAes128,
+    KwAes192, KwAes256, AES_KW_IV, KWP_AIV_PREFIX,
 };
 use hex_literal::hex;
 use std::assert_eq;
@@ -17,9 +18,13 @@ macro_rules! test_aes_kw {
             assert_eq!($pt, pt);
 
             let ct = kw.wrap_fixed_key::<$n>((&$pt).try_into().unwrap());
-            assert_eq!($ct, ct.0);
+            assert_eq!($ct, ct);
             let pt = kw.unwrap_fixed_key::<$n>(&ct).unwrap();
-            assert_eq!($pt, pt.0);
+            assert_eq!($pt, pt);
+
+            let mut ct = ct;
+            let pt = kw.unwrap_fixed_key_in_place::<$n>(&mut ct).unwrap();
+            assert_eq!($pt, *pt);
         }
     };
 }
@@ -73,6 +78,18 @@ test_aes_kw!(
     hex!("28C9F404C4B810F4CBCCB35CFB87F8263F5786E2D80ED326CBC7F0E71A99F43BFB988B9B7A02DD21")
 );
 
+// Single semiblock (n = 1) is outside of the SP 800-38F domain, but
+// the RFC 3394 index-based procedure is still well defined for it.
+// The expected value was computed with an independent implementation.
+test_aes_kw!(
+    wrap_unwrap_64_key_128_kek,
+    KwAes128,
+    U8,
+    hex!("000102030405060708090A0B0C0D0E0F"),
+    hex!("0011223344556677"),
+    hex!("B82669CA42CB86233B5E5CFEACEE620B")
+);
+
 #[test]
 fn error_invalid_data_size() {
     let key = hex!("000102030405060708090A0B0C0D0E0F");
@@ -86,10 +103,16 @@ fn error_invalid_data_size() {
     assert_eq!(res, Err(Error::InvalidDataSize));
 
     let res = kek.unwrap_key(&output, &mut buf);
-    assert_eq!(res, Err(Error::InvalidDataSize));
+    assert_eq!(res, Err(Error::NotSemiblockAligned));
 
     let res = kek.unwrap_key(&[], &mut buf);
+    assert_eq!(res, Err(Error::TooShort { min: 16 }));
+
+    let res = kek.wrap_key(&[], &mut buf);
     assert_eq!(res, Err(Error::InvalidDataSize));
+
+    let res = kek.unwrap_key(&output[..8], &mut buf);
+    assert_eq!(res, Err(Error::TooShort { min: 16 }));
 }
 
 #[test]
@@ -121,3 +144,1180 @@ fn error_integrity_check_failed() {
 
     assert_eq!(res, Err(Error::IntegrityCheckFailed));
 }
+
+#[test]
+fn wrap_unwrap_keys() {
+    let key = hex!("000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F");
+    let input1 = hex!("00112233445566778899AABBCCDDEEFF");
+    let input2 = hex!("00112233445566778899AABBCCDDEEFF0001020304050607");
+    let output1 = hex!("64E8C3F9CE0F5BA263E9777905818A2A93C8191E7D6E8AE7");
+    let output2 = hex!("A8F9BC1612C68B3FF6E6F4FBE30E71E4769C8B80A32CB8958CD5D17D6B254DA1");
+
+    let kek = KwAes256::new(&key.into());
+
+    let mut buf1 = [0u8; 24];
+    let mut buf2 = [0u8; 40];
+    kek.wrap_keys(&[&input1, &input2], &mut [&mut buf1, &mut buf2])
+        .unwrap();
+    assert_eq!(buf1, output1);
+    assert_eq!(buf2[..32], output2);
+
+    let mut buf1 = [0u8; 16];
+    let mut buf2 = [0u8; 24];
+    kek.unwrap_keys(&[&output1, &output2], &mut [&mut buf1, &mut buf2])
+        .unwrap();
+    assert_eq!(buf1, input1);
+    assert_eq!(buf2, input2);
+}
+
+#[test]
+fn error_wrap_unwrap_keys() {
+    let key = hex!("000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F");
+    let input = hex!("00112233445566778899AABBCCDDEEFF");
+    let output = hex!("64E8C3F9CE0F5BA263E9777905818A2A93C8191E7D6E8AE7");
+    let bad_output = hex!("64E8C3F9CE0F5BA263E9777905818A2A93C8191E7D6E8AE8");
+
+    let kek = KwAes256::new(&key.into());
+
+    let mut buf1 = [0u8; 24];
+    let mut buf2 = [0u8; 24];
+    let res = kek.wrap_keys(&[&input, &input[..15]], &mut [&mut buf1, &mut buf2]);
+    assert_eq!(
+        res,
+        Err(BatchError {
+            index: 1,
+            error: Error::InvalidDataSize
+        })
+    );
+    assert_eq!(buf1, [0u8; 24]);
+
+    let res = kek.wrap_keys(&[&input, &input], &mut [&mut buf1]);
+    assert_eq!(
+        res,
+        Err(BatchError {
+            index: 1,
+            error: Error::InvalidOutputSize { expected_len: 24 }
+        })
+    );
+
+    let mut buf1 = [0u8; 16];
+    let mut buf2 = [0u8; 16];
+    let res = kek.unwrap_keys(&[&output, &bad_output], &mut [&mut buf1, &mut buf2]);
+    assert_eq!(
+        res,
+        Err(BatchError {
+            index: 1,
+            error: Error::IntegrityCheckFailed
+        })
+    );
+    assert_eq!(buf1, input);
+    assert_eq!(buf2, [0u8; 16]);
+}
+
+#[test]
+fn from_key_array() {
+    let input = hex!("00112233445566778899AABBCCDDEEFF");
+    let mut buf = [0u8; 24];
+
+    let kek = KwAes128::from(hex!("000102030405060708090A0B0C0D0E0F"));
+    let res = kek.wrap_key(&input, &mut buf).unwrap();
+    assert_eq!(
+        res,
+        hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5")
+    );
+
+    let kek = KwAes192::from(hex!("000102030405060708090A0B0C0D0E0F1011121314151617"));
+    let res = kek.wrap_key(&input, &mut buf).unwrap();
+    assert_eq!(
+        res,
+        hex!("96778B25AE6CA435F92B5B97C050AED2468AB8A17AD84E5D")
+    );
+
+    let kek = KwAes256::from(hex!(
+        "000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F"
+    ));
+    let res = kek.wrap_key(&input, &mut buf).unwrap();
+    assert_eq!(
+        res,
+        hex!("64E8C3F9CE0F5BA263E9777905818A2A93C8191E7D6E8AE7")
+    );
+}
+
+#[test]
+fn try_from_slice() {
+    let key = hex!("000102030405060708090A0B0C0D0E0F101112131415161718191A1B1C1D1E1F");
+    let input = hex!("00112233445566778899AABBCCDDEEFF");
+    let mut buf = [0u8; 24];
+
+    let kek = KwAes128::try_from(&key[..16]).unwrap();
+    let res = kek.wrap_key(&input, &mut buf).unwrap();
+    assert_eq!(
+        res,
+        hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5")
+    );
+
+    let kek = KwAes192::try_from(&key[..24]).unwrap();
+    let res = kek.wrap_key(&input, &mut buf).unwrap();
+    assert_eq!(
+        res,
+        hex!("96778B25AE6CA435F92B5B97C050AED2468AB8A17AD84E5D")
+    );
+
+    let kek = KwAes256::try_from(&key[..]).unwrap();
+    let res = kek.wrap_key(&input, &mut buf).unwrap();
+    assert_eq!(
+        res,
+        hex!("64E8C3F9CE0F5BA263E9777905818A2A93C8191E7D6E8AE7")
+    );
+
+    let res = KwAes128::try_from(&key[..]);
+    assert_eq!(res.err(), Some(Error::InvalidKekSize { bits: 256 }));
+    let res = KwAes256::try_from(&key[..15]);
+    assert_eq!(res.err(), Some(Error::InvalidKekSize { bits: 120 }));
+}
+
+#[test]
+fn jwe_alg() {
+    use aes_kw::JweAlg;
+
+    assert_eq!(KwAes128::JWE_ALG.as_str(), "A128KW");
+    assert_eq!(KwAes192::JWE_ALG.as_str(), "A192KW");
+    assert_eq!(KwAes256::JWE_ALG.as_str(), "A256KW");
+
+    for alg in [JweAlg::A128Kw, JweAlg::A192Kw, JweAlg::A256Kw] {
+        assert_eq!(JweAlg::from_str(alg.as_str()), Some(alg));
+    }
+    assert_eq!(JweAlg::from_str("A128GCMKW"), None);
+    assert_eq!(JweAlg::from_str("a128kw"), None);
+
+    let key = hex!("000102030405060708090A0B0C0D0E0F");
+    let alg = JweAlg::from_str("A128KW").unwrap();
+    assert_eq!(alg.key_len(), key.len());
+    assert!(KwAes128::try_from(&key[..alg.key_len()]).is_ok());
+}
+
+#[test]
+#[cfg(feature = "base64")]
+fn wrap_unwrap_b64() {
+    let kek = hex!("000102030405060708090A0B0C0D0E0F");
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let wkey = "H6aLCoEStEeu80vY-1p7gp0-hiNx0s_l";
+    let kw = KwAes128::new(&kek.into());
+
+    assert_eq!(kw.wrap_key_b64(&key).unwrap(), wkey);
+    assert_eq!(kw.unwrap_key_b64(wkey).unwrap(), key);
+
+    assert_eq!(kw.wrap_key_b64(&key[..7]), Err(Error::InvalidDataSize));
+    assert_eq!(
+        kw.unwrap_key_b64("H6aLCoEStEeu80vY+1p7gp0+hiNx0s/l"),
+        Err(Error::InvalidEncoding)
+    );
+    assert_eq!(kw.unwrap_key_b64("AAAA"), Err(Error::NotSemiblockAligned));
+    assert_eq!(
+        kw.unwrap_key_b64("H6aLCoEStEeu80vY-1p7gp0-hiNx0s_k"),
+        Err(Error::IntegrityCheckFailed)
+    );
+}
+
+#[test]
+fn wrap_unwrap_semiblocks() {
+    let kek = hex!("000102030405060708090A0B0C0D0E0F");
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+    let kw = KwAes128::new(&kek.into());
+
+    // With the default IV `W` matches the RFC 3394 wrapping
+    let mut a = AES_KW_IV;
+    let mut data = key;
+    kw.wrap_semiblocks(&mut a, &mut data).unwrap();
+    assert_eq!(a, wkey[..8]);
+    assert_eq!(data, wkey[8..]);
+
+    kw.unwrap_semiblocks(&mut a, &mut data).unwrap();
+    assert_eq!(a, AES_KW_IV);
+    assert_eq!(data, key);
+
+    // Custom initial value round-trips
+    let iv = hex!("0123456789ABCDEF");
+    let mut a = iv;
+    kw.wrap_semiblocks(&mut a, &mut data).unwrap();
+    assert_ne!(data, wkey[8..]);
+    kw.unwrap_semiblocks(&mut a, &mut data).unwrap();
+    assert_eq!(a, iv);
+    assert_eq!(data, key);
+
+    // A single semiblock is processed like an 8-byte key by `wrap_key`
+    let mut a = AES_KW_IV;
+    let mut data = [0x42; 8];
+    kw.wrap_semiblocks(&mut a, &mut data).unwrap();
+    let mut buf = [0u8; 16];
+    let res = kw.wrap_key(&[0x42; 8], &mut buf).unwrap();
+    assert_eq!(res, [a, data].concat());
+    kw.unwrap_semiblocks(&mut a, &mut data).unwrap();
+    assert_eq!((a, data), (AES_KW_IV, [0x42; 8]));
+
+    let mut data = [0u8; 12];
+    let res = kw.wrap_semiblocks(&mut a, &mut data);
+    assert_eq!(res, Err(Error::InvalidDataSize));
+    let res = kw.unwrap_semiblocks(&mut a, &mut []);
+    assert_eq!(res, Err(Error::InvalidDataSize));
+}
+
+#[test]
+fn get_inner() {
+    use aes_kw::{aes::Aes128, cipher::BlockCipherEncrypt, InnerInit};
+
+    let kek = hex!("000102030405060708090A0B0C0D0E0F");
+    let cipher = Aes128::new(&kek.into());
+    let kw = KwAes128::inner_init(cipher.clone());
+
+    let mut block1 = [0x42; 16].into();
+    let mut block2 = [0x42; 16].into();
+    cipher.encrypt_block(&mut block1);
+    kw.get_inner().encrypt_block(&mut block2);
+    assert_eq!(block1, block2);
+
+    let inner: &Aes128 = kw.as_ref();
+    inner.encrypt_block(&mut block2);
+    cipher.encrypt_block(&mut block1);
+    assert_eq!(block1, block2);
+}
+
+#[test]
+fn iv_constants() {
+    assert_eq!(AES_KW_IV, hex!("A6A6A6A6A6A6A6A6"));
+    assert_eq!(KWP_AIV_PREFIX, hex!("A65959A6"));
+
+    // RFC 5649 § 6: KWP output is `W` applied with the AIV
+    let kek = hex!("5840DF6E29B02AF1AB493B705BF16EA1AE8338F4DCC176A8");
+    let wkey = hex!("138BDEAA9B8FA7FC61F97742E72248EE5AE6AE5360D1AE6A5F54F373FA543B6A");
+    let kw = KwAes192::new(&kek.into());
+
+    let mut a = wkey[..8].try_into().unwrap();
+    let mut data = wkey[8..].to_vec();
+    kw.unwrap_semiblocks(&mut a, &mut data).unwrap();
+    assert_eq!(a[..4], KWP_AIV_PREFIX);
+    assert_eq!(a[4..], 20u32.to_be_bytes());
+    assert_eq!(data[..20], hex!("C37B7E6492584340BED12207808941155068F738"));
+}
+
+#[test]
+fn invalid_kek_size_display() {
+    let err = KwAes192::try_from(&[0u8; 20][..]).unwrap_err();
+    assert_eq!(err, Error::InvalidKekSize { bits: 160 });
+    assert_ne!(err, Error::InvalidKekSize { bits: 192 });
+    assert_eq!(err.to_string(), "160-bit KEK is not supported");
+}
+
+#[test]
+fn invalid_wrapped_len_display() {
+    assert_eq!(
+        Error::NotSemiblockAligned.to_string(),
+        "data length must be a multiple of the semiblock size"
+    );
+    assert_eq!(
+        Error::TooShort { min: 16 }.to_string(),
+        "wrapped data is too short: expected at least 16 bytes"
+    );
+    assert_eq!(
+        Error::LengthOverflow.to_string(),
+        "wrapped data length overflows usize"
+    );
+}
+
+#[test]
+fn unwrap_key_exact() {
+    let kek = hex!("000102030405060708090A0B0C0D0E0F");
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+    let kw = KwAes128::new(&kek.into());
+
+    let mut buf = [0u8; 16];
+    let res = kw.unwrap_key_exact(&wkey, &mut buf).unwrap();
+    let mut expected = [0u8; 16];
+    assert_eq!(res, kw.unwrap_key(&wkey, &mut expected).unwrap());
+
+    let mut scratch = [0u8; 64];
+    let res = kw.unwrap_key_exact(&wkey, &mut scratch[..17]);
+    assert_eq!(res, Err(Error::InvalidOutputSize { expected_len: 16 }));
+    let res = kw.unwrap_key_exact(&wkey, &mut scratch[..15]);
+    assert_eq!(res, Err(Error::InvalidOutputSize { expected_len: 16 }));
+    let res = kw.unwrap_key_exact(&wkey[1..], &mut buf);
+    assert_eq!(res, Err(Error::NotSemiblockAligned));
+}
+
+#[test]
+fn wrap_key_into() {
+    let kek = hex!("000102030405060708090A0B0C0D0E0F");
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let kw = KwAes128::new(&kek.into());
+
+    let mut buf = [0u8; 32];
+    let n = kw.wrap_key_into(&key, &mut buf).unwrap();
+    assert_eq!(n, 24);
+    assert_eq!(
+        buf[..n],
+        hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5")
+    );
+    assert_eq!(buf[n..], [0u8; 8]);
+
+    let res = kw.wrap_key_into(&key, &mut buf[..23]);
+    assert_eq!(res, Err(Error::InvalidOutputSize { expected_len: 24 }));
+}
+
+#[test]
+fn debug_is_redacted() {
+    let kw = KwAes128::new(&[0x42; 16].into());
+    assert_eq!(format!("{kw:?}"), "AesKw { ... }");
+}
+
+#[test]
+fn integrity_check_failed_into_error() {
+    fn unwrap(kw: &KwAes128, wkey: &[u8; 24]) -> Result<[u8; 16], Error> {
+        let key = kw.unwrap_fixed_key::<U16>(wkey.into())?;
+        Ok(key.into())
+    }
+
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let mut wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+    assert_eq!(
+        unwrap(&kw, &wkey),
+        Ok(hex!("00112233445566778899AABBCCDDEEFF"))
+    );
+    wkey[0] ^= 1;
+    assert_eq!(unwrap(&kw, &wkey), Err(Error::IntegrityCheckFailed));
+}
+
+#[test]
+fn fixed_key_builtin_arrays() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let key: [u8; 16] = hex!("00112233445566778899AABBCCDDEEFF");
+
+    let wkey: [u8; 24] = kw.wrap_fixed_key::<U16>(&key.into()).into();
+    assert_eq!(
+        wkey,
+        hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5")
+    );
+    let res: [u8; 16] = kw.unwrap_fixed_key::<U16>(&wkey.into()).unwrap().into();
+    assert_eq!(res, key);
+}
+
+#[test]
+fn wrap_key_ref() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+    let mut buf = [0u8; 24];
+
+    assert_eq!(kw.wrap_key_ref(key, &mut buf).unwrap(), wkey);
+    assert_eq!(kw.wrap_key_ref(Vec::from(key), &mut buf).unwrap(), wkey);
+    let boxed: Box<[u8]> = key.into();
+    assert_eq!(kw.wrap_key_ref(&boxed, &mut buf).unwrap(), wkey);
+}
+
+#[test]
+#[cfg(feature = "heapless")]
+fn wrap_key_heapless() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+
+    let wkey = kw.wrap_key_heapless::<32>(&key).unwrap();
+    assert_eq!(
+        wkey,
+        hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5")
+    );
+
+    let res = kw.wrap_key_heapless::<23>(&key);
+    assert_eq!(res, Err(Error::InvalidOutputSize { expected_len: 24 }));
+}
+
+#[test]
+fn length_constants() {
+    assert_eq!(KwAes128::OVERHEAD, 8);
+    assert_eq!(KwAes128::SEMIBLOCK_LEN, 8);
+    assert_eq!(KwAes128::block_size(), 2 * KwAes128::SEMIBLOCK_LEN);
+    assert_eq!(KwAes128::MIN_KEY_LEN, 8);
+    assert_eq!(KwAes128::MAX_KEY_LEN % 8, 0);
+    assert!(KwAes128::MAX_KEY_LEN
+        .checked_add(KwAes128::OVERHEAD)
+        .is_some());
+
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let key = [0x42; KwAes128::MIN_KEY_LEN];
+    let mut buf = [0u8; KwAes128::MIN_KEY_LEN + KwAes128::OVERHEAD];
+    assert!(kw.wrap_key(&key, &mut buf).is_ok());
+}
+
+#[test]
+fn required_lens() {
+    assert_eq!(KwAes128::required_wrap_len(16), Ok(24));
+    assert_eq!(KwAes128::required_wrap_len(8), Ok(16));
+    assert_eq!(KwAes128::required_wrap_len(0), Err(Error::InvalidDataSize));
+    assert_eq!(KwAes128::required_wrap_len(15), Err(Error::InvalidDataSize));
+    assert_eq!(
+        KwAes128::required_wrap_len(KwAes128::MAX_KEY_LEN),
+        Ok(KwAes128::MAX_KEY_LEN + 8)
+    );
+    assert_eq!(
+        KwAes128::required_wrap_len(usize::MAX - 7),
+        Err(Error::LengthOverflow)
+    );
+
+    assert_eq!(KwAes128::required_unwrap_len(24), Ok(16));
+    assert_eq!(
+        KwAes128::required_unwrap_len(8),
+        Err(Error::TooShort { min: 16 })
+    );
+    assert_eq!(
+        KwAes128::required_unwrap_len(23),
+        Err(Error::NotSemiblockAligned)
+    );
+}
+
+#[test]
+fn wrap_unwrap_detached() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+
+    let mut iv = [0u8; 8];
+    let mut body = [0u8; 16];
+    kw.wrap_key_detached(&key, &mut iv, &mut body).unwrap();
+    assert_eq!(iv, wkey[..8]);
+    assert_eq!(body, wkey[8..]);
+
+    let mut buf = [0u8; 16];
+    let res = kw.unwrap_key_detached(&iv, &body, &mut buf).unwrap();
+    assert_eq!(res, key);
+
+    iv[0] ^= 1;
+    let res = kw.unwrap_key_detached(&iv, &body, &mut buf);
+    assert_eq!(res, Err(Error::IntegrityCheckFailed));
+    assert_eq!(buf, [0u8; 16]);
+
+    let res = kw.wrap_key_detached(&key, &mut iv, &mut body[..15]);
+    assert_eq!(res, Err(Error::InvalidOutputSize { expected_len: 16 }));
+    let res = kw.unwrap_key_detached(&iv, &body[..15], &mut buf);
+    assert_eq!(res, Err(Error::NotSemiblockAligned));
+}
+
+#[test]
+fn block_cipher_kw_alias() {
+    use aes_kw::{aes::Aes128, BlockCipherKw};
+
+    let kw = BlockCipherKw::<Aes128>::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let mut buf = [0u8; 24];
+    let res = kw
+        .wrap_key(&hex!("00112233445566778899AABBCCDDEEFF"), &mut buf)
+        .unwrap();
+    assert_eq!(
+        res,
+        hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5")
+    );
+}
+
+#[test]
+#[cfg(feature = "subtle")]
+fn wrapped_key_ct_eq() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let stored = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+    let mut buf = [0u8; 24];
+    let wkey = kw
+        .wrap_key(&hex!("00112233445566778899AABBCCDDEEFF"), &mut buf)
+        .unwrap();
+
+    assert!(bool::from(aes_kw::ct_eq(wkey, &stored)));
+    let mut other = stored;
+    other[23] ^= 1;
+    assert!(!bool::from(aes_kw::ct_eq(wkey, &other)));
+    assert!(!bool::from(aes_kw::ct_eq(wkey, &stored[..23])));
+}
+
+#[test]
+fn rekey() {
+    let mut kw = KwAes128::new(&hex!("00000000000000000000000000000000").into());
+    let mut buf = [0u8; 24];
+    let res = kw
+        .wrap_key(&hex!("00112233445566778899AABBCCDDEEFF"), &mut buf)
+        .unwrap();
+    assert_ne!(
+        res,
+        hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5")
+    );
+
+    kw.rekey(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let res = kw
+        .wrap_key(&hex!("00112233445566778899AABBCCDDEEFF"), &mut buf)
+        .unwrap();
+    assert_eq!(
+        res,
+        hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5")
+    );
+}
+
+#[test]
+fn unwrap_cipher() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+
+    let inner: KwAes128 = kw.unwrap_cipher(&wkey).unwrap();
+    let expected = KwAes128::new(&hex!("00112233445566778899AABBCCDDEEFF").into());
+    let key = hex!("000102030405060708090A0B0C0D0E0F");
+    assert_eq!(
+        inner.wrap_fixed_key::<U16>(&key.into()),
+        expected.wrap_fixed_key::<U16>(&key.into()),
+    );
+
+    let res = kw.unwrap_cipher::<KwAes256>(&wkey);
+    assert_eq!(res.err(), Some(Error::InvalidDataSize));
+
+    let mut bad = wkey;
+    bad[0] ^= 1;
+    let res = kw.unwrap_cipher::<KwAes128>(&bad);
+    assert_eq!(res.err(), Some(Error::IntegrityCheckFailed));
+}
+
+#[test]
+fn wrap_unwrap_cipher_key() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+
+    let mut buf = [0u8; 24];
+    let res = kw
+        .wrap_cipher_key::<KwAes128>(&key.into(), &mut buf)
+        .unwrap();
+    assert_eq!(res, wkey);
+
+    let res = kw.unwrap_cipher_key::<KwAes128>(&wkey).unwrap();
+    assert_eq!(res, key);
+
+    let res = kw.unwrap_cipher_key::<KwAes192>(&wkey);
+    assert_eq!(res, Err(Error::InvalidDataSize));
+}
+
+#[test]
+fn wrap_unwrap_in_place() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+
+    let mut buf = [0u8; 24];
+    buf[8..].copy_from_slice(&key);
+    kw.wrap_key_in_place(&mut buf).unwrap();
+    assert_eq!(buf, wkey);
+
+    assert_eq!(kw.unwrap_key_in_place(&mut buf).unwrap(), key);
+
+    let mut buf = wkey;
+    buf[0] ^= 1;
+    let res = kw.unwrap_key_in_place(&mut buf);
+    assert_eq!(res, Err(Error::IntegrityCheckFailed));
+    assert_eq!(buf, [0u8; 24]);
+
+    let res = kw.wrap_key_in_place(&mut [0u8; 8]);
+    assert_eq!(res, Err(Error::InvalidDataSize));
+    let res = kw.wrap_key_in_place(&mut [0u8; 20]);
+    assert_eq!(res, Err(Error::InvalidDataSize));
+    let res = kw.unwrap_key_in_place(&mut [0u8; 8]).map(|_| ());
+    assert_eq!(res, Err(Error::TooShort { min: 16 }));
+}
+
+#[test]
+fn unwrap_fixed_key_as() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+
+    let key = kw.unwrap_fixed_key_as::<KwAes128>(&wkey.into()).unwrap();
+    assert_eq!(key, hex!("00112233445566778899AABBCCDDEEFF"));
+    let _ = KwAes128::new(&key);
+
+    let mut bad = wkey;
+    bad[0] ^= 1;
+    let res = kw.unwrap_fixed_key_as::<KwAes128>(&bad.into());
+    assert_eq!(res, Err(IntegrityCheckFailed));
+}
+
+#[test]
+#[cfg(feature = "rand_core")]
+fn generate() {
+    use aes_kw::rand_core::{CryptoRng, Error, RngCore};
+
+    struct CounterRng(u8);
+
+    impl RngCore for CounterRng {
+        fn next_u32(&mut self) -> u32 {
+            unimplemented!()
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            unimplemented!()
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for b in dest {
+                *b = self.0;
+                self.0 += 1;
+            }
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl CryptoRng for CounterRng {}
+
+    let kw = KwAes128::generate(&mut CounterRng(0));
+    let mut buf = [0u8; 24];
+    let res = kw
+        .wrap_key(&hex!("00112233445566778899AABBCCDDEEFF"), &mut buf)
+        .unwrap();
+    assert_eq!(
+        res,
+        hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5")
+    );
+}
+
+#[test]
+#[cfg(feature = "getrandom")]
+fn generate_kek() {
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let mut buf1 = [0u8; 24];
+    let mut buf2 = [0u8; 24];
+    let res1 = KwAes256::generate_kek()
+        .unwrap()
+        .wrap_key(&key, &mut buf1)
+        .unwrap();
+    let res2 = KwAes256::generate_kek()
+        .unwrap()
+        .wrap_key(&key, &mut buf2)
+        .unwrap();
+    assert_ne!(res1, res2);
+}
+
+#[test]
+fn wrap_unwrap_pgp() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    // Symmetric algorithm ID, session key, and checksum as in RFC 6637 § 8
+    let key = hex!("0900112233445566778899AABBCCDDEEFF00112233445566778899AABBCCDDEEFF1234");
+    let wkey = hex!(
+        "B78743C8A5979635A7E2742A0F6A473991756D206D2EE3D7"
+        "6301F13E6785764D017E67877CEB86AD64F2931DC09BA3C4"
+    );
+
+    let mut buf = [0u8; 48];
+    assert_eq!(kw.wrap_pgp(&key, &mut buf).unwrap(), wkey);
+    assert_eq!(kw.unwrap_pgp(&wkey, &mut buf).unwrap(), key);
+
+    // Aligned keys get a full semiblock of padding
+    let key = hex!("000102030405060708090A0B0C0D0E0F");
+    let wkey = hex!("010506605AB646A5A30054BC1EFDB0BED88DEAB07F37FB5A6376FF769EB9976B");
+    assert_eq!(kw.wrap_pgp(&key, &mut buf).unwrap(), wkey);
+    assert_eq!(kw.unwrap_pgp(&wkey, &mut buf).unwrap(), key);
+
+    for len in 0..=33 {
+        let key = [0x42; 33];
+        let res = kw.wrap_pgp(&key[..len], &mut buf).unwrap();
+        assert_eq!(res.len(), (len / 8 + 2) * 8);
+        let wkey = res.to_vec();
+        assert_eq!(kw.unwrap_pgp(&wkey, &mut buf).unwrap(), &key[..len]);
+    }
+
+    let res = kw.wrap_pgp(&key, &mut buf[..31]);
+    assert_eq!(res, Err(Error::InvalidOutputSize { expected_len: 32 }));
+}
+
+#[test]
+fn unwrap_pgp_bad_padding() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let mut buf = [0u8; 24];
+    for padded in [
+        hex!("00112233445566778899AABBCCDDEE00"),
+        hex!("00112233445566778899AABBCCDDEE09"),
+        hex!("00112233445566778899AABBCCDD0303"),
+        hex!("00112233445566778899AABBCCDDEEFF"),
+    ] {
+        let wkey = kw.wrap_key(&padded, &mut buf).unwrap().to_vec();
+        let res = kw.unwrap_pgp(&wkey, &mut buf);
+        assert_eq!(res, Err(Error::IntegrityCheckFailed));
+        assert_eq!(buf[..16], [0u8; 16]);
+    }
+}
+
+#[test]
+fn unwrap_fixed_key_in_place_zeroes_on_failure() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let mut wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+    wkey[0] ^= 1;
+    let mut wkey = wkey.into();
+    let res = kw.unwrap_fixed_key_in_place::<U16>(&mut wkey);
+    assert_eq!(res, Err(IntegrityCheckFailed));
+    assert_eq!(wkey, [0u8; 24]);
+}
+
+#[test]
+fn unwrap_key_with() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let mut wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+    let mut scratch = [0u8; 32];
+
+    let res = kw.unwrap_key_with(&wkey, &mut scratch, |key| key.to_vec());
+    assert_eq!(res.unwrap(), hex!("00112233445566778899AABBCCDDEEFF"));
+    assert_eq!(scratch, [0u8; 32]);
+
+    wkey[0] ^= 1;
+    let res = kw.unwrap_key_with(&wkey, &mut scratch, |_| unreachable!());
+    assert_eq!(res, Err::<(), _>(Error::IntegrityCheckFailed));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn shared_aes_kw() {
+    use aes_kw::{aes::Aes128, SharedAesKw};
+
+    let kek = hex!("000102030405060708090A0B0C0D0E0F");
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let expected = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+
+    let kw = SharedAesKw::<Aes128>::new(&kek.into());
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let kw = kw.clone();
+            std::thread::spawn(move || {
+                let mut buf = [0u8; 24];
+                kw.wrap_key(&key, &mut buf).unwrap();
+                assert_eq!(buf, expected);
+                let mut out = [0u8; 16];
+                assert_eq!(kw.unwrap_key(&buf, &mut out).unwrap(), key);
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    let kw2 = SharedAesKw::from(KwAes128::new(&kek.into()));
+    assert_eq!(kw2.wrap_fixed_key::<U16>(&key.into()), expected);
+}
+
+#[test]
+fn hex_wrapped() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let wkey = kw.wrap_fixed_key::<U16>(&hex!("00112233445566778899AABBCCDDEEFF").into());
+    assert_eq!(
+        HexWrapped(&wkey).to_string(),
+        "1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5"
+    );
+    assert_eq!(HexWrapped(&[]).to_string(), "");
+}
+
+#[test]
+fn unwrap_key_uninit() {
+    use core::mem::MaybeUninit;
+
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+
+    let mut buf = [MaybeUninit::<u8>::uninit(); 20];
+    let res = kw.unwrap_key_uninit(&wkey, &mut buf).unwrap();
+    assert_eq!(res, hex!("00112233445566778899AABBCCDDEEFF"));
+
+    let mut buf = [MaybeUninit::<u8>::uninit(); 16];
+    let mut bad = wkey;
+    bad[0] ^= 1;
+    let res = kw.unwrap_key_uninit(&bad, &mut buf);
+    assert_eq!(res, Err(Error::IntegrityCheckFailed));
+
+    let res = kw.unwrap_key_uninit(&wkey, &mut buf[..15]);
+    assert_eq!(res, Err(Error::InvalidOutputSize { expected_len: 16 }));
+}
+
+#[test]
+#[cfg(feature = "zeroize")]
+fn unwrap_fixed_key_secret() {
+    use aes_kw::zeroize::Zeroize;
+
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+
+    let mut key = kw.unwrap_fixed_key_secret::<U16>(&wkey.into()).unwrap();
+    assert_eq!(*key, hex!("00112233445566778899AABBCCDDEEFF"));
+    assert_eq!(format!("{key:?}"), "SecretKey { ... }");
+    key.zeroize();
+    assert_eq!(*key, [0u8; 16]);
+
+    let mut bad = wkey;
+    bad[0] ^= 1;
+    let res = kw.unwrap_fixed_key_secret::<U16>(&bad.into());
+    assert_eq!(res.err(), Some(IntegrityCheckFailed));
+}
+
+#[test]
+fn wrap_unwrap_with_backend() {
+    use aes_kw::{
+        aes::Aes128,
+        cipher::{
+            consts::U1, Block, BlockCipherDecBackend, BlockCipherDecrypt, BlockCipherEncBackend,
+            BlockCipherEncrypt, BlockSizeUser, InOut, ParBlocksSizeUser,
+        },
+        unwrap_with_backend, wrap_with_backend,
+    };
+
+    // Backend which forwards single blocks, e.g. to an HSM
+    struct Remote(Aes128);
+
+    impl BlockSizeUser for Remote {
+        type BlockSize = U16;
+    }
+
+    impl ParBlocksSizeUser for Remote {
+        type ParBlocksSize = U1;
+    }
+
+    impl BlockCipherEncBackend for Remote {
+        fn encrypt_block(&self, mut block: InOut<'_, '_, Block<Self>>) {
+            let mut b = block.clone_in();
+            BlockCipherEncrypt::encrypt_block(&self.0, &mut b);
+            *block.get_out() = b;
+        }
+    }
+
+    impl BlockCipherDecBackend for Remote {
+        fn decrypt_block(&self, mut block: InOut<'_, '_, Block<Self>>) {
+            let mut b = block.clone_in();
+            BlockCipherDecrypt::decrypt_block(&self.0, &mut b);
+            *block.get_out() = b;
+        }
+    }
+
+    let kek = hex!("000102030405060708090A0B0C0D0E0F");
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+    let backend = Remote(Aes128::new(&kek.into()));
+
+    let mut buf = [0u8; 24];
+    buf[8..].copy_from_slice(&key);
+    wrap_with_backend(&backend, &AES_KW_IV, &mut buf).unwrap();
+    assert_eq!(buf, wkey);
+
+    unwrap_with_backend(&backend, &AES_KW_IV, &mut buf).unwrap();
+    assert_eq!(buf[..8], AES_KW_IV);
+    assert_eq!(buf[8..], key);
+
+    let mut buf = wkey;
+    let res = unwrap_with_backend(&backend, &[0; 8], &mut buf);
+    assert_eq!(res, Err(Error::IntegrityCheckFailed));
+    assert_eq!(buf, [0u8; 24]);
+
+    let res = wrap_with_backend(&backend, &AES_KW_IV, &mut [0u8; 8]);
+    assert_eq!(res, Err(Error::TooShort { min: 16 }));
+    let res = wrap_with_backend(&backend, &AES_KW_IV, &mut [0u8; 20]);
+    assert_eq!(res, Err(Error::NotSemiblockAligned));
+}
+
+#[test]
+fn dyn_key_wrap() {
+    use aes_kw::{KeyWrap, KeyWrapEncrypt, KwpAes128};
+
+    let kek = hex!("000102030405060708090A0B0C0D0E0F");
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+
+    let kw = KwAes128::new(&kek.into());
+    let kwp = KwpAes128::new(&kek.into());
+    let mut buf = [0u8; 24];
+    let kw_wkey = kw.wrap_key(&key, &mut buf).unwrap().to_vec();
+    let kwp_wkey = kwp.wrap_key(&key, &mut buf).unwrap().to_vec();
+
+    let encryptors: [Box<dyn KeyWrapEncrypt>; 2] = [Box::new(kw.clone()), Box::new(kwp.clone())];
+    for (enc, expected) in encryptors.iter().zip([&kw_wkey, &kwp_wkey]) {
+        assert_eq!(enc.wrap_len(key.len()), Ok(24));
+        let res = enc.wrap_key_default(&key, &mut buf).unwrap();
+        assert_eq!(res, &expected[..]);
+        let res = enc.wrap_key_with_iv(&key, &[0; 3], &mut buf);
+        let expected_len = enc.iv_len();
+        assert_eq!(res, Err(Error::InvalidIvSize { expected_len }));
+    }
+
+    #[allow(unused_mut)]
+    let mut algs: Vec<Box<dyn KeyWrap>> = vec![Box::new(kw), Box::new(kwp)];
+    #[cfg(feature = "belt-kwp")]
+    algs.push(Box::new(aes_kw::belt_kwp::BeltKwp::new(
+        &hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6").into(),
+    )));
+
+    let mut wbuf = [0u8; 32];
+    let mut buf = [0u8; 32];
+    for alg in &algs {
+        let iv_len = KeyWrapEncrypt::iv_len(&**alg);
+        let iv = &[0x42; 16][..iv_len];
+        let wkey = alg.wrap_key_with_iv(&key, iv, &mut wbuf).unwrap();
+
+        let res = alg.unwrap_key_with_iv(wkey, iv, &mut buf).unwrap();
+        assert_eq!(res, key);
+
+        let res = alg.unwrap_key_default(wkey, &mut buf);
+        assert_eq!(res, Err(Error::IntegrityCheckFailed));
+        let n = alg.unwrap_len(wkey.len()).unwrap();
+        assert!(buf[..n].iter().all(|&b| b == 0));
+    }
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn wrap_keys_par() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let keys: Vec<Vec<u8>> = (0..64u8)
+        .map(|i| vec![i; 16 + 8 * (i as usize % 4)])
+        .collect();
+
+    let res = kw.wrap_keys_par(&keys).unwrap();
+    assert_eq!(res.len(), keys.len());
+    for (key, wkey) in keys.iter().zip(&res) {
+        let mut buf = [0u8; 48];
+        assert_eq!(kw.wrap_key(key, &mut buf).unwrap(), &wkey[..]);
+    }
+
+    let keys: [&[u8]; 2] = [&[0; 16], &[0; 15]];
+    assert_eq!(kw.wrap_keys_par(&keys), Err(Error::InvalidDataSize));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn verify() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let mut wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+    assert_eq!(kw.verify(&wkey), Ok(()));
+
+    wkey[23] ^= 1;
+    assert_eq!(kw.verify(&wkey), Err(Error::IntegrityCheckFailed));
+    assert_eq!(kw.verify(&wkey[..20]), Err(Error::NotSemiblockAligned));
+}
+
+#[test]
+#[cfg(feature = "debug-errors")]
+fn unwrap_key_debug() {
+    use aes_kw::KwpAes128;
+
+    let kek = hex!("000102030405060708090A0B0C0D0E0F");
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+    let kw = KwAes128::new(&kek.into());
+    let mut buf = [0u8; 16];
+
+    assert_eq!(kw.unwrap_key_debug(&wkey, &mut buf).unwrap(), key);
+
+    // Wrapped key with the integrity register `A[0]` set to zero
+    let mut a = [0u8; 8];
+    let mut wkey = key;
+    kw.wrap_semiblocks(&mut a, &mut wkey).unwrap();
+    let wkey = [a.as_slice(), &wkey].concat();
+    let err = kw.unwrap_key_debug(&wkey, &mut buf).unwrap_err();
+    assert_eq!(err, Error::IntegrityCheckFailedDebug { computed: [0; 8] });
+    assert_eq!(buf, [0u8; 16]);
+    assert_eq!(
+        err.to_string(),
+        "integrity check failed: computed integrity register 0000000000000000"
+    );
+
+    // AES-KWP unwrapping of an AES-KW wrapped key exposes the default IV
+    let kwp = KwpAes128::new(&kek.into());
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+    let res = kwp.unwrap_key_debug(&wkey, &mut buf);
+    assert_eq!(
+        res,
+        Err(Error::IntegrityCheckFailedDebug {
+            computed: AES_KW_IV
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "secrecy")]
+fn wrap_unwrap_secret() {
+    use aes_kw::secrecy::{ExposeSecret, SecretVec};
+
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let key = SecretVec::new(hex!("00112233445566778899AABBCCDDEEFF").to_vec());
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+
+    assert_eq!(kw.wrap_secret(&key).unwrap(), wkey);
+    let res = kw.unwrap_secret(&wkey).unwrap();
+    assert_eq!(res.expose_secret(), key.expose_secret());
+
+    let res = kw.unwrap_secret(&wkey[1..]);
+    assert!(matches!(res, Err(Error::NotSemiblockAligned)));
+    let res = kw.wrap_secret(&SecretVec::new(vec![0; 15]));
+    assert_eq!(res, Err(Error::InvalidDataSize));
+}
+
+#[test]
+fn wrap_unwrap_key_twice() {
+    let inner = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let outer = KwAes128::new(&hex!("0F0E0D0C0B0A09080706050403020100").into());
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+
+    let mut buf1 = [0u8; 24];
+    let mut buf2 = [0u8; 32];
+    let expected = inner.wrap_key(&key, &mut buf1).unwrap();
+    let expected = outer.wrap_key(expected, &mut buf2).unwrap();
+
+    let mut wkey = [0u8; 32];
+    let res = inner.wrap_key_twice(&outer, &key, &mut wkey).unwrap();
+    assert_eq!(res, expected);
+
+    let mut buf = [0u8; 24];
+    let res = inner.unwrap_key_twice(&outer, &wkey, &mut buf).unwrap();
+    assert_eq!(res, key);
+
+    // Layers must be removed in the reverse order
+    let res = outer.unwrap_key_twice(&inner, &wkey, &mut buf);
+    assert_eq!(res, Err(Error::IntegrityCheckFailed));
+    assert_eq!(buf, [0u8; 24]);
+
+    let res = inner.wrap_key_twice(&outer, &key, &mut wkey[..31]);
+    assert_eq!(res, Err(Error::InvalidOutputSize { expected_len: 32 }));
+    let res = inner.unwrap_key_twice(&outer, &wkey, &mut buf[..16]);
+    assert_eq!(res, Err(Error::InvalidOutputSize { expected_len: 24 }));
+    let res = inner.unwrap_key_twice(&outer, &wkey[..16], &mut buf);
+    assert_eq!(res, Err(Error::TooShort { min: 24 }));
+}
+
+#[test]
+fn wrap_unwrap_array() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+
+    let res: [u8; 24] = kw.wrap_array(&key);
+    assert_eq!(res, wkey);
+    assert_eq!(kw.unwrap_array(&wkey), Ok(key));
+
+    let mut bad = wkey;
+    bad[0] ^= 1;
+    assert_eq!(kw.unwrap_array::<16, 24>(&bad), Err(IntegrityCheckFailed));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn wrap_key_append() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+
+    let mut out = b"header".to_vec();
+    kw.wrap_key_append(&key, &mut out).unwrap();
+    assert_eq!(out[..6], *b"header");
+    assert_eq!(out[6..], wkey);
+
+    let res = kw.wrap_key_append(&key[..15], &mut out);
+    assert_eq!(res, Err(Error::InvalidDataSize));
+    assert_eq!(out.len(), 6 + wkey.len());
+}
+
+#[test]
+#[cfg(feature = "zeroize")]
+fn wrap_key_consuming() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let mut key = hex!("00112233445566778899AABBCCDDEEFF");
+    let mut buf = [0u8; 24];
+
+    let res = kw.wrap_key_consuming(&mut key, &mut buf[..23]);
+    assert_eq!(res, Err(Error::InvalidOutputSize { expected_len: 24 }));
+    assert_eq!(key, hex!("00112233445566778899AABBCCDDEEFF"));
+
+    let res = kw.wrap_key_consuming(&mut key, &mut buf).unwrap();
+    assert_eq!(
+        res,
+        hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5")
+    );
+    assert_eq!(key, [0u8; 16]);
+}
+
+#[test]
+fn semiblocks() {
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+    let blocks: Vec<&[u8; 8]> = aes_kw::semiblocks(&wkey).unwrap().collect();
+    assert_eq!(
+        blocks,
+        [
+            &hex!("1FA68B0A8112B447"),
+            &hex!("AEF34BD8FB5A7B82"),
+            &hex!("9D3E862371D2CFE5"),
+        ]
+    );
+
+    assert_eq!(aes_kw::semiblocks(&[]).unwrap().count(), 0);
+    assert!(matches!(
+        aes_kw::semiblocks(&wkey[..23]),
+        Err(Error::NotSemiblockAligned)
+    ));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn integrity_tag() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let tag = kw
+        .integrity_tag(&hex!("00112233445566778899AABBCCDDEEFF"))
+        .unwrap();
+    assert_eq!(tag, hex!("1FA68B0A8112B447"));
+
+    assert_eq!(kw.integrity_tag(&[0; 15]), Err(Error::InvalidDataSize));
+}
+
+#[test]
+fn one_directional() {
+    use aes_kw::{KwAes128Dec, KwAes128Enc};
+
+    let kek = hex!("000102030405060708090A0B0C0D0E0F");
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+
+    let mut buf = [0u8; 24];
+    let res = KwAes128Enc::new(&kek.into())
+        .wrap_key(&key, &mut buf)
+        .unwrap();
+    assert_eq!(res, wkey);
+
+    let mut buf = [0u8; 16];
+    let res = KwAes128Dec::new(&kek.into())
+        .unwrap_key(&wkey, &mut buf)
+        .unwrap();
+    assert_eq!(res, key);
+}
+
+#[test]
+fn with_cipher_ref() {
+    use aes_kw::{aes::Aes128, AesKw, AesKwRef};
+
+    let kek = hex!("000102030405060708090A0B0C0D0E0F");
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+
+    let ciphers = [Aes128::new(&[0; 16].into()), Aes128::new(&kek.into())];
+    let kw: AesKwRef<'_, Aes128> = AesKw::with_cipher_ref(&ciphers[1]);
+
+    let mut buf = [0u8; 24];
+    assert_eq!(kw.wrap_key(&key, &mut buf).unwrap(), wkey);
+    let mut buf = [0u8; 16];
+    assert_eq!(kw.unwrap_key(&wkey, &mut buf).unwrap(), key);
+}
+
+#[test]
+#[cfg(feature = "bytes")]
+fn wrap_unwrap_bytes() {
+    let kw = KwAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let key = hex!("00112233445566778899AABBCCDDEEFF");
+    let wkey = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+
+    let res = kw.wrap_key_bytes(&key).unwrap();
+    assert_eq!(res, wkey[..]);
+    let res = kw.unwrap_key_bytes(&res).unwrap();
+    assert_eq!(res, key[..]);
+
+    assert_eq!(kw.wrap_key_bytes(&key[..15]), Err(Error::InvalidDataSize));
+    let mut bad = wkey;
+    bad[0] ^= 1;
+    assert_eq!(kw.unwrap_key_bytes(&bad), Err(Error::IntegrityCheckFailed));
+}
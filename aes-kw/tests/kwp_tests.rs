@@ -1,4 +1,7 @@
-use aes_kw::{cipher::consts, Error, KeyInit, KwpAes128, KwpAes192, KwpAes256};
+use aes_kw::{
+    cipher::consts, BlockSizeUser, Error, IntegrityCheckFailed, KeyInit, KwpAes128, KwpAes192,
+    KwpAes256,
+};
 use hex_literal::hex;
 use std::assert_eq;
 
@@ -14,9 +17,13 @@ macro_rules! test_aes_kwp {
             assert_eq!($pt, pt);
 
             let ct = kwp.wrap_fixed_key::<$n>((&$pt).try_into().unwrap());
-            assert_eq!($ct, ct.0);
+            assert_eq!($ct, ct);
             let pt = kwp.unwrap_fixed_key::<$n>(&ct).unwrap();
-            assert_eq!($pt, pt.0);
+            assert_eq!($pt, pt);
+
+            let mut ct = ct;
+            let pt = kwp.unwrap_fixed_key_in_place::<$n>(&mut ct).unwrap();
+            assert_eq!($pt, *pt);
         }
     };
 }
@@ -164,10 +171,15 @@ fn error_invalid_data_size() {
     let kwp = KwpAes128::new(&key.into());
 
     let mut buf = [0u8; 16];
-    let res = kwp.unwrap_key(&output, &mut buf);
+    let res = kwp.wrap_key(&[], &mut buf);
     assert_eq!(res, Err(Error::InvalidDataSize));
+
+    let res = kwp.unwrap_key(&output, &mut buf);
+    assert_eq!(res, Err(Error::NotSemiblockAligned));
     let res = kwp.unwrap_key(&[], &mut buf);
-    assert_eq!(res, Err(Error::InvalidDataSize));
+    assert_eq!(res, Err(Error::TooShort { min: 16 }));
+    let res = kwp.unwrap_key(&output[..8], &mut buf);
+    assert_eq!(res, Err(Error::TooShort { min: 16 }));
 }
 
 #[test]
@@ -220,3 +232,437 @@ fn error_integrity_check_failed() {
     let res = kek.unwrap_key(&output, &mut buf);
     assert_eq!(res, Err(Error::IntegrityCheckFailed));
 }
+
+#[test]
+fn from_key_array() {
+    let mut buf = [0u8; 16];
+
+    let kwp = KwpAes128::from(hex!("AF83AE6624FC006DA13B3C37B8A5933B"));
+    let res = kwp.wrap_key(&hex!("13126A"), &mut buf).unwrap();
+    assert_eq!(res, hex!("A661F530339C9F344FA4755AD4CC3558"));
+
+    let kwp = KwpAes192::from(hex!("BA0CFC260103DDD629FA8826982F5547D245F5AB0711F10F"));
+    let res = kwp.wrap_key(&hex!("C01990"), &mut buf).unwrap();
+    assert_eq!(res, hex!("91E3B5E73A25EC91E91D337D0485B960"));
+
+    let kwp = KwpAes256::from(hex!(
+        "6D60C0D0941CF3750B864C6F1FA580AE074C00EDEB386F9FC299178A70FCCCD1"
+    ));
+    let res = kwp.wrap_key(&hex!("6B54A0"), &mut buf).unwrap();
+    assert_eq!(res, hex!("24255140B4A9F8A9E35B9DA2BFA0E0C3"));
+}
+
+#[test]
+fn try_from_slice() {
+    let key = hex!("AF83AE6624FC006DA13B3C37B8A5933B");
+    let mut buf = [0u8; 16];
+
+    let kwp = KwpAes128::try_from(&key[..]).unwrap();
+    let res = kwp.wrap_key(&hex!("13126A"), &mut buf).unwrap();
+    assert_eq!(res, hex!("A661F530339C9F344FA4755AD4CC3558"));
+
+    let res = KwpAes192::try_from(&key[..]);
+    assert_eq!(res.err(), Some(Error::InvalidKekSize { bits: 128 }));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn reader_writer() {
+    use std::io::ErrorKind;
+
+    let kek = hex!("5840DF6E29B02AF1AB493B705BF16EA1AE8338F4DCC176A8");
+    let key = hex!("C37B7E6492584340BED12207808941155068F738");
+    let wkey = hex!("138BDEAA9B8FA7FC61F97742E72248EE5AE6AE5360D1AE6A5F54F373FA543B6A");
+    let kwp = KwpAes192::new(&kek.into());
+
+    let mut out = Vec::new();
+    let n = kwp.wrap_key_from_reader(&key[..], &mut out).unwrap();
+    assert_eq!(n, wkey.len());
+    assert_eq!(out, wkey);
+
+    let mut out = Vec::new();
+    let n = kwp.unwrap_key_to_writer(&wkey, &mut out).unwrap();
+    assert_eq!(n, key.len());
+    assert_eq!(out, key);
+
+    let mut bad_wkey = wkey;
+    bad_wkey[0] ^= 1;
+    let mut out = Vec::new();
+    let err = kwp.unwrap_key_to_writer(&bad_wkey, &mut out).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    assert!(out.is_empty());
+
+    let err = kwp.wrap_key_from_reader(&[][..], &mut out).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+    assert!(out.is_empty());
+
+    // key spanning several read chunks and buffer growths
+    let long_key: Vec<u8> = (0..1000).map(|i| i as u8).collect();
+    let mut out = Vec::new();
+    kwp.wrap_key_from_reader(&long_key[..], &mut out).unwrap();
+    let mut expected = Vec::new();
+    kwp.wrap_key_append(&long_key, &mut expected).unwrap();
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn unwrap_key_exact() {
+    let kek = hex!("5840DF6E29B02AF1AB493B705BF16EA1AE8338F4DCC176A8");
+    let wkey = hex!("138BDEAA9B8FA7FC61F97742E72248EE5AE6AE5360D1AE6A5F54F373FA543B6A");
+    let kw = KwpAes192::new(&kek.into());
+
+    let mut buf = [0u8; 24];
+    let res = kw.unwrap_key_exact(&wkey, &mut buf).unwrap();
+    let mut expected = [0u8; 24];
+    assert_eq!(res, kw.unwrap_key(&wkey, &mut expected).unwrap());
+
+    let mut scratch = [0u8; 64];
+    let res = kw.unwrap_key_exact(&wkey, &mut scratch[..25]);
+    assert_eq!(res, Err(Error::InvalidOutputSize { expected_len: 24 }));
+    let res = kw.unwrap_key_exact(&wkey, &mut scratch[..23]);
+    assert_eq!(res, Err(Error::InvalidOutputSize { expected_len: 24 }));
+    let res = kw.unwrap_key_exact(&wkey[1..], &mut buf);
+    assert_eq!(res, Err(Error::NotSemiblockAligned));
+}
+
+#[test]
+fn wrap_key_into() {
+    let kek = hex!("5840DF6E29B02AF1AB493B705BF16EA1AE8338F4DCC176A8");
+    let key = hex!("466F7250617369");
+    let kwp = KwpAes192::new(&kek.into());
+
+    let mut buf = [0u8; 32];
+    let n = kwp.wrap_key_into(&key, &mut buf).unwrap();
+    assert_eq!(n, 16);
+    assert_eq!(buf[..n], hex!("AFBEB0F07DFBF5419200F2CCB50BB24F"));
+
+    let res = kwp.wrap_key_into(&key, &mut buf[..15]);
+    assert_eq!(res, Err(Error::InvalidOutputSize { expected_len: 16 }));
+}
+
+#[test]
+fn debug_is_redacted() {
+    let kw = KwpAes128::new(&[0x42; 16].into());
+    assert_eq!(format!("{kw:?}"), "AesKwp { ... }");
+}
+
+#[test]
+fn wrap_key_ref() {
+    let kek = hex!("5840DF6E29B02AF1AB493B705BF16EA1AE8338F4DCC176A8");
+    let kwp = KwpAes192::new(&kek.into());
+    let key = vec![0x46, 0x6F, 0x72, 0x50, 0x61, 0x73, 0x69];
+    let mut buf = [0u8; 16];
+
+    let res = kwp.wrap_key_ref(&key, &mut buf).unwrap();
+    assert_eq!(res, hex!("AFBEB0F07DFBF5419200F2CCB50BB24F"));
+}
+
+#[test]
+fn length_constants() {
+    assert_eq!(KwpAes128::OVERHEAD, 8);
+    assert_eq!(KwpAes128::SEMIBLOCK_LEN, 8);
+    assert_eq!(KwpAes256::block_size(), 2 * KwpAes256::SEMIBLOCK_LEN);
+    assert_eq!(KwpAes128::MIN_KEY_LEN, 1);
+    assert_eq!(KwpAes128::MAX_KEY_LEN, u32::MAX as usize);
+}
+
+#[test]
+fn single_block() {
+    use aes_kw::{
+        aes::Aes128,
+        cipher::{BlockCipherEncrypt, KeyInit as _},
+    };
+
+    assert!(!KwpAes128::is_single_block(0));
+    assert!(KwpAes128::is_single_block(1));
+    assert!(KwpAes128::is_single_block(8));
+    assert!(!KwpAes128::is_single_block(9));
+
+    let kek = hex!("AF83AE6624FC006DA13B3C37B8A5933B");
+    let kwp = KwpAes128::new(&kek.into());
+    let aes = Aes128::new(&kek.into());
+
+    // AIV || P || PAD is encrypted as a single block
+    let mut block = hex!("A65959A600000003 13126A0000000000").into();
+    aes.encrypt_block(&mut block);
+    assert_eq!(block, hex!("A661F530339C9F344FA4755AD4CC3558"));
+    let wkey = kwp.wrap_fixed_key::<consts::U3>(&hex!("13126A").into());
+    assert_eq!(wkey, block);
+
+    let mut block = hex!("A65959A600000008 4202C90D7298CB4B").into();
+    aes.encrypt_block(&mut block);
+    let wkey = kwp.wrap_fixed_key::<consts::U8>(&hex!("4202C90D7298CB4B").into());
+    assert_eq!(wkey, block);
+    let key = kwp.unwrap_fixed_key::<consts::U8>(&wkey).unwrap();
+    assert_eq!(key, hex!("4202C90D7298CB4B"));
+}
+
+#[test]
+fn zero_mli() {
+    use aes_kw::KwAes128;
+
+    let kek = hex!("AF83AE6624FC006DA13B3C37B8A5933B");
+    let kwp = KwpAes128::new(&kek.into());
+    let mut buf = [0u8; 16];
+
+    // Encryption of `A65959A6 00000000 || 0000000000000000` as a single block
+    let wkey = hex!("6905578CBE8C27E0694F908A40045C60");
+    let res = kwp.unwrap_key(&wkey, &mut buf);
+    assert_eq!(res, Err(Error::IntegrityCheckFailed));
+    assert_eq!(buf, [0u8; 16]);
+
+    // Two semiblocks wrapped with AIV `A65959A6 00000000`
+    let mut aiv = hex!("A65959A600000000");
+    let mut data = [0u8; 16];
+    KwAes128::new(&kek.into())
+        .wrap_semiblocks(&mut aiv, &mut data)
+        .unwrap();
+    let wkey = [aiv.as_slice(), &data].concat();
+    let res = kwp.unwrap_key(&wkey, &mut buf);
+    assert_eq!(res, Err(Error::IntegrityCheckFailed));
+    assert_eq!(buf, [0u8; 16]);
+}
+
+#[test]
+fn unwrap_single_block_slice() {
+    use aes_kw::{aes::Aes128, cipher::BlockCipherDecrypt};
+
+    let kek = hex!("AF83AE6624FC006DA13B3C37B8A5933B");
+    let kwp = KwpAes128::new(&kek.into());
+    let wkey = hex!("A661F530339C9F344FA4755AD4CC3558");
+
+    // The single-block path decrypts AIV || P || PAD with one block cipher call
+    let mut block = wkey.into();
+    Aes128::new(&kek.into()).decrypt_block(&mut block);
+    assert_eq!(block, hex!("A65959A600000003 13126A0000000000"));
+
+    assert_eq!(KwpAes128::required_unwrap_len(wkey.len()), Ok(8));
+    let mut buf = [0u8; 8];
+    assert_eq!(kwp.unwrap_key(&wkey, &mut buf).unwrap(), hex!("13126A"));
+
+    let res = kwp.unwrap_key(&wkey, &mut buf[..7]);
+    assert_eq!(res, Err(Error::InvalidOutputSize { expected_len: 8 }));
+
+    let mut bad = wkey;
+    bad[15] ^= 1;
+    let mut buf = [0xFF; 8];
+    let res = kwp.unwrap_key(&bad, &mut buf);
+    assert_eq!(res, Err(Error::IntegrityCheckFailed));
+    assert_eq!(buf, [0u8; 8]);
+}
+
+#[test]
+fn mli_with_full_semiblock_of_padding() {
+    let kwp = KwpAes128::new(&hex!("000102030405060708090A0B0C0D0E0F").into());
+    let mut buf = [0u8; 16];
+
+    // Two semiblocks with MLI = 16
+    let wkey = hex!("3860FBE0D6FED623DC18996D9BE2EAB8B33BA4106B9846FC");
+    let res = kwp.unwrap_key(&wkey, &mut buf).unwrap();
+    assert_eq!(res, hex!("00112233445566770000000000000000"));
+
+    // The same semiblocks with MLI = 8, i.e. with a full semiblock of zero padding
+    let wkey = hex!("DE0E85502AD1281B5E9B1A0ABB093B3080FEBC8312263D33");
+    let res = kwp.unwrap_key(&wkey, &mut buf);
+    assert_eq!(res, Err(Error::IntegrityCheckFailed));
+    assert_eq!(buf, [0u8; 16]);
+}
+
+#[test]
+fn wrap_into_dirty_buffer() {
+    let kek = hex!("5840df6e29b02af1ab493b705bf16ea1ae8338f4dcc176a8");
+    let kwp = KwpAes192::new(&kek.into());
+    let key = hex!("c37b7e6492584340bed12207808941155068f738");
+    let wkey = hex!("138bdeaa9b8fa7fc61f97742e72248ee5ae6ae5360d1ae6a5f54f373fa543b6a");
+
+    // Padding must not depend on the previous content of the buffer
+    let mut buf = [0xFF; 32];
+    assert_eq!(kwp.wrap_key(&key, &mut buf).unwrap(), wkey);
+}
+
+#[test]
+fn required_lens() {
+    assert_eq!(KwpAes128::required_wrap_len(1), Ok(16));
+    assert_eq!(KwpAes128::required_wrap_len(8), Ok(16));
+    assert_eq!(KwpAes128::required_wrap_len(9), Ok(24));
+    assert_eq!(KwpAes128::required_wrap_len(20), Ok(32));
+    assert_eq!(KwpAes128::required_wrap_len(0), Err(Error::InvalidDataSize));
+    let res = KwpAes128::required_wrap_len(KwpAes128::MAX_KEY_LEN + 1);
+    assert_eq!(res, Err(Error::InvalidDataSize));
+
+    assert_eq!(KwpAes128::required_unwrap_len(16), Ok(8));
+    assert_eq!(KwpAes128::required_unwrap_len(32), Ok(24));
+    assert_eq!(
+        KwpAes128::required_unwrap_len(0),
+        Err(Error::TooShort { min: 16 })
+    );
+    assert_eq!(
+        KwpAes128::required_unwrap_len(17),
+        Err(Error::NotSemiblockAligned)
+    );
+}
+
+#[test]
+fn rekey() {
+    let mut kw = KwpAes192::new(&hex!("000000000000000000000000000000000000000000000000").into());
+    let mut buf = [0u8; 24];
+    let res = kw.wrap_key(&hex!("466F7250617369"), &mut buf).unwrap();
+    assert_ne!(res, hex!("AFBEB0F07DFBF5419200F2CCB50BB24F"));
+
+    kw.rekey(&hex!("5840DF6E29B02AF1AB493B705BF16EA1AE8338F4DCC176A8").into());
+    let res = kw.wrap_key(&hex!("466F7250617369"), &mut buf).unwrap();
+    assert_eq!(res, hex!("AFBEB0F07DFBF5419200F2CCB50BB24F"));
+}
+
+#[test]
+fn unwrap_fixed_key_mli_mismatch() {
+    let kek = hex!("5840df6e29b02af1ab493b705bf16ea1ae8338f4dcc176a8");
+    let kwp = KwpAes192::new(&kek.into());
+    // Wrapped 7-byte key from RFC 5649
+    let wkey = hex!("afbeb0f07dfbf5419200f2ccb50bb24f");
+
+    let res = kwp.unwrap_fixed_key::<consts::U8>(&wkey.into());
+    assert_eq!(res, Err(IntegrityCheckFailed));
+
+    let mut wkey = wkey.into();
+    let res = kwp.unwrap_fixed_key_in_place::<consts::U8>(&mut wkey);
+    assert_eq!(res, Err(IntegrityCheckFailed));
+    assert_eq!(wkey[8..], [0u8; 8]);
+}
+
+#[test]
+fn unwrap_key_with() {
+    let kek = hex!("5840df6e29b02af1ab493b705bf16ea1ae8338f4dcc176a8");
+    let kwp = KwpAes192::new(&kek.into());
+    let wkey = hex!("138bdeaa9b8fa7fc61f97742e72248ee5ae6ae5360d1ae6a5f54f373fa543b6a");
+    let mut scratch = [0u8; 24];
+
+    let res = kwp.unwrap_key_with(&wkey, &mut scratch, |key| key.to_vec());
+    assert_eq!(
+        res.unwrap(),
+        hex!("c37b7e6492584340bed12207808941155068f738")
+    );
+    assert_eq!(scratch, [0u8; 24]);
+}
+
+#[test]
+#[cfg(feature = "zeroize")]
+fn unwrap_fixed_key_secret() {
+    let kek = hex!("5840df6e29b02af1ab493b705bf16ea1ae8338f4dcc176a8");
+    let kwp = KwpAes192::new(&kek.into());
+    let wkey = hex!("afbeb0f07dfbf5419200f2ccb50bb24f");
+
+    let key = kwp
+        .unwrap_fixed_key_secret::<consts::U7>(&wkey.into())
+        .unwrap();
+    assert_eq!(key.as_ref(), hex!("466f7250617369"));
+
+    let res = kwp.unwrap_fixed_key_secret::<consts::U8>(&wkey.into());
+    assert_eq!(res.err(), Some(IntegrityCheckFailed));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn verify() {
+    // RFC 5649 § 6 test vector
+    let kek = hex!("5840df6e29b02af1 ab493b705bf16ea1 ae8338f4dcc176a8");
+    let mut wkey = hex!("afbeb0f07dfbf541 9200f2ccb50bb24f");
+    let kwp = KwpAes192::new(&kek.into());
+    assert_eq!(kwp.verify(&wkey), Ok(()));
+
+    wkey[0] ^= 1;
+    assert_eq!(kwp.verify(&wkey), Err(Error::IntegrityCheckFailed));
+}
+
+#[test]
+fn wrap_unwrap_array() {
+    let kwp = KwpAes192::new(&hex!("5840DF6E29B02AF1AB493B705BF16EA1AE8338F4DCC176A8").into());
+    let key = hex!("C37B7E6492584340BED12207808941155068F738");
+    let wkey = hex!("138BDEAA9B8FA7FC61F97742E72248EE5AE6AE5360D1AE6A5F54F373FA543B6A");
+
+    let res: [u8; 32] = kwp.wrap_array(&key);
+    assert_eq!(res, wkey);
+    assert_eq!(kwp.unwrap_array(&wkey), Ok(key));
+
+    // MLI does not match `N`
+    assert_eq!(kwp.unwrap_array::<17, 32>(&wkey), Err(IntegrityCheckFailed));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn wrap_key_append() {
+    let kwp = KwpAes192::new(&hex!("5840DF6E29B02AF1AB493B705BF16EA1AE8338F4DCC176A8").into());
+    let key = hex!("466F7250617369");
+    let wkey = hex!("AFBEB0F07DFBF5419200F2CCB50BB24F");
+
+    let mut out = b"header".to_vec();
+    kwp.wrap_key_append(&key, &mut out).unwrap();
+    assert_eq!(out[..6], *b"header");
+    assert_eq!(out[6..], wkey);
+
+    let res = kwp.wrap_key_append(&[], &mut out);
+    assert_eq!(res, Err(Error::InvalidDataSize));
+    assert_eq!(out.len(), 6 + wkey.len());
+}
+
+#[test]
+#[cfg(feature = "zeroize")]
+fn wrap_key_consuming() {
+    let kwp = KwpAes192::new(&hex!("5840DF6E29B02AF1AB493B705BF16EA1AE8338F4DCC176A8").into());
+    let mut key = hex!("466F7250617369");
+    let mut buf = [0u8; 16];
+
+    let res = kwp.wrap_key_consuming(&mut key, &mut buf).unwrap();
+    assert_eq!(res, hex!("AFBEB0F07DFBF5419200F2CCB50BB24F"));
+    assert_eq!(key, [0u8; 7]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn integrity_tag() {
+    let kwp = KwpAes192::new(&hex!("5840DF6E29B02AF1AB493B705BF16EA1AE8338F4DCC176A8").into());
+
+    let tag = kwp.integrity_tag(&hex!("466F7250617369")).unwrap();
+    assert_eq!(tag, hex!("AFBEB0F07DFBF541"));
+
+    let tag = kwp
+        .integrity_tag(&hex!("C37B7E6492584340BED12207808941155068F738"))
+        .unwrap();
+    assert_eq!(tag, hex!("138BDEAA9B8FA7FC"));
+
+    assert_eq!(kwp.integrity_tag(&[]), Err(Error::InvalidDataSize));
+}
+
+#[test]
+fn one_directional() {
+    use aes_kw::{KwpAes192Dec, KwpAes192Enc};
+
+    let kek = hex!("5840DF6E29B02AF1AB493B705BF16EA1AE8338F4DCC176A8");
+    let key = hex!("466F7250617369");
+    let wkey = hex!("AFBEB0F07DFBF5419200F2CCB50BB24F");
+
+    let mut buf = [0u8; 16];
+    let res = KwpAes192Enc::new(&kek.into())
+        .wrap_key(&key, &mut buf)
+        .unwrap();
+    assert_eq!(res, wkey);
+
+    let res = KwpAes192Dec::new(&kek.into())
+        .unwrap_key(&wkey, &mut buf)
+        .unwrap();
+    assert_eq!(res, key);
+}
+
+#[test]
+fn with_cipher_ref() {
+    use aes_kw::{aes::Aes192, AesKwp};
+
+    let cipher = Aes192::new(&hex!("5840DF6E29B02AF1AB493B705BF16EA1AE8338F4DCC176A8").into());
+    let kwp = AesKwp::with_cipher_ref(&cipher);
+    let key = hex!("466F7250617369");
+    let wkey = hex!("AFBEB0F07DFBF5419200F2CCB50BB24F");
+
+    let mut buf = [0u8; 16];
+    assert_eq!(kwp.wrap_key(&key, &mut buf).unwrap(), wkey);
+    assert_eq!(kwp.unwrap_key(&wkey, &mut buf).unwrap(), key);
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "magma")]
+
+use aes_kw::{CryptoProA, IntegrityCheckFailed, KeyInit, MagmaKeyWrap, Tc26};
+use hex_literal::hex;
+
+// RFC 4357 does not provide test vectors for the key wrap algorithms, so the vectors
+// below were generated using an independent implementation. Its GOST 28147-89 core
+// was checked against the Magma test vector from GOST R 34.12-2015 (using the
+// 28147-89 byte order), its `gost28147IMIT` against the GOST 28147-89 MAC test
+// vectors of the CryptoPro-A parameter set (see the unit tests in `gost_kw.rs`)
+// and CryptoPro KEK diversification follows RFC 4357 § 6.5.
+const KEK: [u8; 32] = hex!("202122232425262728292A2B2C2D2E2F303132333435363738393A3B3C3D3E3F");
+const UKM: [u8; 8] = hex!("0102030405060708");
+const CEK: [u8; 32] = hex!("404142434445464748494A4B4C4D4E4F505152535455565758595A5B5C5D5E5F");
+
+#[test]
+fn cryptopro_key_wrap() {
+    let kw = MagmaKeyWrap::<CryptoProA>::new(&KEK.into());
+    let wkey = kw.wrap_key(&UKM, &CEK);
+    assert_eq!(
+        wkey,
+        hex!(
+            "0102030405060708"
+            "861529BC21EEC9FFABFD09CFAB165C6BE3755BEFAA62689176DDE49413F88DEB"
+            "E5FB9946"
+        )
+    );
+    assert_eq!(kw.unwrap_key(&wkey), Ok(CEK));
+
+    // Diversification depends on the S-box
+    let kw = MagmaKeyWrap::<Tc26>::new(&KEK.into());
+    let wkey = kw.wrap_key(&UKM, &CEK);
+    assert_eq!(
+        wkey,
+        hex!(
+            "0102030405060708"
+            "AA0B1D4CB0EAC18DCA3842F1CE5198182DFE8A059D48BC6A1724CDABEB388C37"
+            "1E3574DF"
+        )
+    );
+    assert_eq!(kw.unwrap_key(&wkey), Ok(CEK));
+}
+
+/// CEK_ENC is computed using ECB, so the first block can be checked against the
+/// Magma test vector from GOST R 34.12-2015 § A.2 converted to the GOST 28147-89
+/// byte order, i.e. with every key word and the whole block byte-reversed.
+#[test]
+fn gost_r_34_12_2015_vector() {
+    let kek = hex!("CCDDEEFF8899AABB4455667700112233F3F2F1F0F7F6F5F4FBFAF9F8FFFEFDFC");
+    let mut cek = [0u8; 32];
+    cek[..8].copy_from_slice(&hex!("1032547698BADCFE"));
+
+    let kw = MagmaKeyWrap::<Tc26>::new(&kek.into());
+    let wkey = kw.wrap_key_gost89(&UKM, &cek);
+    assert_eq!(wkey[8..16], hex!("3DCAD8C2E501E94E"));
+    assert_eq!(kw.unwrap_key_gost89(&wkey), Ok(cek));
+}
+
+#[test]
+fn gost89_key_wrap() {
+    let kw: MagmaKeyWrap = MagmaKeyWrap::new(&KEK.into());
+    let wkey = kw.wrap_key_gost89(&UKM, &CEK);
+    assert_eq!(
+        wkey,
+        hex!(
+            "0102030405060708"
+            "427731FA6F4BA00CAB55CCD793E0CBF2C07BDC1ED501D8BD85054BF378B70A74"
+            "DF985434"
+        )
+    );
+    assert_eq!(kw.unwrap_key_gost89(&wkey), Ok(CEK));
+    assert_eq!(kw.unwrap_key(&wkey), Err(IntegrityCheckFailed));
+}
+
+#[test]
+fn unwrap_failure() {
+    let kw: MagmaKeyWrap = MagmaKeyWrap::new(&KEK.into());
+    let wkey = kw.wrap_key(&UKM, &CEK);
+    for i in [0, 8, 39, 40, 43] {
+        let mut bad = wkey;
+        bad[i] ^= 1;
+        assert_eq!(kw.unwrap_key(&bad), Err(IntegrityCheckFailed));
+    }
+}
+
+#[test]
+fn lengths() {
+    assert_eq!(MagmaKeyWrap::<CryptoProA>::UKM_LEN, 8);
+    assert_eq!(MagmaKeyWrap::<CryptoProA>::CEK_LEN, 32);
+    assert_eq!(MagmaKeyWrap::<CryptoProA>::MAC_LEN, 4);
+    assert_eq!(MagmaKeyWrap::<CryptoProA>::WRAPPED_LEN, 44);
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "oid")]
+
+use aes_kw::{
+    const_oid::{AssociatedOid, ObjectIdentifier},
+    kek_len_for_oid, key_wrap_for_oid, KeyWrapAlgorithm, KwAes128, KwAes192, KwAes256, KwpAes128,
+    KwpAes192, KwpAes256,
+};
+
+#[test]
+fn key_wrap_for_oid_round_trip() {
+    let cases = [
+        (KwAes128::OID, KeyWrapAlgorithm::KwAes128),
+        (KwAes192::OID, KeyWrapAlgorithm::KwAes192),
+        (KwAes256::OID, KeyWrapAlgorithm::KwAes256),
+        (KwpAes128::OID, KeyWrapAlgorithm::KwpAes128),
+        (KwpAes192::OID, KeyWrapAlgorithm::KwpAes192),
+        (KwpAes256::OID, KeyWrapAlgorithm::KwpAes256),
+    ];
+
+    for (oid, alg) in cases {
+        assert_eq!(key_wrap_for_oid(&oid), Some(alg));
+    }
+
+    // AES-128-GCM
+    let oid = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.1.6");
+    assert_eq!(key_wrap_for_oid(&oid), None);
+}
+
+#[test]
+fn key_wrap_algorithm_properties() {
+    let alg = key_wrap_for_oid(&KwpAes192::OID).unwrap();
+    assert!(alg.is_padded());
+    assert_eq!(alg.kek_len(), 24);
+
+    let alg = key_wrap_for_oid(&KwAes256::OID).unwrap();
+    assert!(!alg.is_padded());
+    assert_eq!(alg.kek_len(), 32);
+}
+
+#[test]
+fn key_wrap_algorithm_oid() {
+    assert_eq!(KeyWrapAlgorithm::KwAes128.oid(), KwAes128::OID);
+    assert_eq!(KeyWrapAlgorithm::KwpAes256.oid(), KwpAes256::OID);
+    assert_eq!(
+        KeyWrapAlgorithm::from_oid(&KwpAes128::OID),
+        Some(KeyWrapAlgorithm::KwpAes128)
+    );
+}
+
+#[test]
+fn kek_len_for_oid_lookup() {
+    let cases = [
+        (KwAes128::OID, 16),
+        (KwAes192::OID, 24),
+        (KwAes256::OID, 32),
+        (KwpAes128::OID, 16),
+        (KwpAes192::OID, 24),
+        (KwpAes256::OID, 32),
+    ];
+
+    for (oid, len) in cases {
+        assert_eq!(kek_len_for_oid(&oid), Some(len));
+    }
+
+    // AES-128-GCM
+    let oid = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.1.6");
+    assert_eq!(kek_len_for_oid(&oid), None);
+}
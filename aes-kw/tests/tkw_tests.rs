@@ -0,0 +1,94 @@
+#![cfg(feature = "des")]
+
+use aes_kw::{Error, KeyInit, Tkw};
+use hex_literal::hex;
+
+// The vectors below were generated using an independent implementation of the
+// rotating-register form of the `W` function from SP 800-38F § 6.1 (Algorithm 1)
+// over the TDEA implementation of the Python `cryptography` package.
+//
+// TODO: add cases from `TKW_AE.txt` and `TKW_AD.txt` of the NIST CAVP key wrap
+// test vectors (`kwtestvectors.zip`), which cover TKW independently of this
+// implementation.
+const KEK: [u8; 24] = hex!("000102030405060708090A0B0C0D0E0F1011121314151617");
+
+macro_rules! test_tkw {
+    ($name:ident, $key:expr, $wkey:expr) => {
+        #[test]
+        fn $name() {
+            let key = hex!($key);
+            let wkey = hex!($wkey);
+            let tkw = Tkw::new(&KEK.into());
+
+            let mut buf = [0u8; 64];
+            let res = tkw.wrap_key(&key, &mut buf).unwrap();
+            assert_eq!(res, wkey);
+
+            let res = tkw.unwrap_key(&wkey, &mut buf).unwrap();
+            assert_eq!(res, key);
+        }
+    };
+}
+
+test_tkw!(tkw_64, "0011223344556677", "16277D1DB80D82A76DE53A76");
+test_tkw!(
+    tkw_96,
+    "00112233445566778899AABB",
+    "F49FC320848906FBCC3CFA03F47F621C"
+);
+test_tkw!(
+    tkw_192,
+    "00112233445566778899AABBCCDDEEFF0001020304050607",
+    "61D134A9C5BF657C87CDE168C569EDE54F85D220A4B97EB4E860D8DA"
+);
+
+#[test]
+fn errors() {
+    let tkw = Tkw::new(&KEK.into());
+    let mut buf = [0u8; 16];
+
+    assert_eq!(tkw.wrap_key(&[0; 4], &mut buf), Err(Error::InvalidDataSize));
+    assert_eq!(
+        tkw.wrap_key(&[0; 10], &mut buf),
+        Err(Error::InvalidDataSize)
+    );
+    assert_eq!(
+        tkw.wrap_key(&[0; 12], &mut buf[..15]),
+        Err(Error::InvalidOutputSize { expected_len: 16 })
+    );
+
+    let wkey = hex!("16277D1DB80D82A76DE53A76");
+    assert_eq!(
+        tkw.unwrap_key(&wkey[..8], &mut buf),
+        Err(Error::TooShort { min: 12 })
+    );
+    assert_eq!(
+        tkw.unwrap_key(&wkey[..11], &mut buf),
+        Err(Error::NotSemiblockAligned)
+    );
+    assert_eq!(
+        tkw.unwrap_key(&wkey, &mut buf[..7]),
+        Err(Error::InvalidOutputSize { expected_len: 8 })
+    );
+
+    let mut bad = wkey;
+    bad[0] ^= 1;
+    assert_eq!(
+        tkw.unwrap_key(&bad, &mut buf),
+        Err(Error::IntegrityCheckFailed)
+    );
+    assert_eq!(buf, [0u8; 16]);
+}
+
+#[test]
+fn length_constants() {
+    assert_eq!(Tkw::SEMIBLOCK_LEN, 4);
+    assert_eq!(Tkw::OVERHEAD, 4);
+    assert_eq!(Tkw::MIN_KEY_LEN, 8);
+    assert_eq!(Tkw::required_wrap_len(8), Ok(12));
+    assert_eq!(
+        Tkw::required_wrap_len(Tkw::MAX_KEY_LEN + 4),
+        Err(Error::InvalidDataSize)
+    );
+    assert_eq!(Tkw::required_unwrap_len(12), Ok(8));
+}
@@ -0,0 +1,5 @@
+use aes_kw::{des::TdesEde3, AesKw};
+
+fn main() {
+    let _kw: Option<AesKw<TdesEde3>> = None;
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "alloc")]
+
+use aes_kw::{Error, WrappedKey};
+use hex_literal::hex;
+
+const WKEY: [u8; 24] = hex!("1FA68B0A8112B447AEF34BD8FB5A7B829D3E862371D2CFE5");
+
+#[test]
+fn new() {
+    let wkey = WrappedKey::new(WKEY.to_vec()).unwrap();
+    assert_eq!(wkey.as_bytes(), WKEY);
+    assert_eq!(&wkey[..8], &WKEY[..8]);
+    assert_eq!(wkey.len(), 24);
+    assert_eq!(wkey.into_vec(), WKEY);
+
+    assert_eq!(WrappedKey::new(Vec::new()), Err(Error::InvalidDataSize));
+    assert_eq!(
+        WrappedKey::try_from(&WKEY[..23]),
+        Err(Error::InvalidDataSize)
+    );
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde() {
+    use serde_test::{assert_de_tokens_error, assert_tokens, Configure, Token};
+
+    let wkey = WrappedKey::new(WKEY.to_vec()).unwrap();
+    assert_tokens(&wkey.clone().compact(), &[Token::Bytes(&WKEY)]);
+    assert_tokens(
+        &wkey.readable(),
+        &[Token::Str("H6aLCoEStEeu80vY-1p7gp0-hiNx0s_l")],
+    );
+
+    assert_de_tokens_error::<serde_test::Compact<WrappedKey>>(
+        &[Token::Bytes(&WKEY[..23])],
+        "invalid length 23, expected wrapped key with length multiple of 8 bytes",
+    );
+    assert_de_tokens_error::<serde_test::Readable<WrappedKey>>(
+        &[Token::Str("H6aLCoEStEeu80vY-1p7gp0-hiNx0s_")],
+        "invalid value: string \"H6aLCoEStEeu80vY-1p7gp0-hiNx0s_\", \
+         expected wrapped key with length multiple of 8 bytes",
+    );
+}
@@ -0,0 +1,147 @@
+//! Tests using test vectors in the Wycheproof key wrap format
+use aes_kw::{
+    cipher::{consts::U16, BlockCipherDecrypt, BlockCipherEncrypt},
+    AesKw, AesKwp, Error, KeyInit,
+};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestFile {
+    number_of_tests: usize,
+    test_groups: Vec<TestGroup>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestGroup {
+    key_size: usize,
+    tests: Vec<TestCase>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TestCase {
+    tc_id: usize,
+    flags: Vec<String>,
+    key: String,
+    msg: String,
+    ct: String,
+    result: TestResult,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum TestResult {
+    Valid,
+    Acceptable,
+    Invalid,
+}
+
+fn decode_hex(s: &str) -> Vec<u8> {
+    assert!(s.len() % 2 == 0);
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+        .collect()
+}
+
+/// Check that `err` is the error expected for an invalid test case with `flags`.
+fn check_error(tc: &TestCase, err: Error) {
+    let is_size_err = matches!(
+        err,
+        Error::NotSemiblockAligned | Error::TooShort { .. } | Error::InvalidDataSize
+    );
+    if tc.flags.iter().any(|f| f == "InvalidWrappingSize") {
+        assert!(is_size_err, "tcId {}: unexpected error {err:?}", tc.tc_id);
+    } else {
+        assert_eq!(err, Error::IntegrityCheckFailed, "tcId {}", tc.tc_id);
+    }
+}
+
+/// Run test case `tc` using the provided wrap and unwrap functions.
+fn run_test(
+    tc: &TestCase,
+    wrap: impl Fn(&[u8], &mut [u8]) -> Result<usize, Error>,
+    unwrap: impl Fn(&[u8], &mut [u8]) -> Result<usize, Error>,
+) {
+    let msg = decode_hex(&tc.msg);
+    let ct = decode_hex(&tc.ct);
+    let mut buf = vec![0u8; msg.len().max(ct.len()) + 16];
+
+    match tc.result {
+        TestResult::Valid => {
+            let n = wrap(&msg, &mut buf).unwrap();
+            assert_eq!(buf[..n], ct, "tcId {}", tc.tc_id);
+            let n = unwrap(&ct, &mut buf).unwrap();
+            assert_eq!(buf[..n], msg, "tcId {}", tc.tc_id);
+        }
+        TestResult::Acceptable => {
+            if let Ok(n) = wrap(&msg, &mut buf) {
+                assert_eq!(buf[..n], ct, "tcId {}", tc.tc_id);
+            }
+            if let Ok(n) = unwrap(&ct, &mut buf) {
+                assert_eq!(buf[..n], msg, "tcId {}", tc.tc_id);
+            }
+        }
+        TestResult::Invalid => match unwrap(&ct, &mut buf) {
+            Ok(_) => panic!("tcId {}: invalid wrapped key was accepted", tc.tc_id),
+            Err(err) => check_error(tc, err),
+        },
+    }
+}
+
+fn run_kw<C>(tc: &TestCase)
+where
+    C: KeyInit + BlockCipherEncrypt<BlockSize = U16> + BlockCipherDecrypt<BlockSize = U16>,
+{
+    let kw = AesKw::<C>::try_from(&decode_hex(&tc.key)[..]).unwrap();
+    run_test(
+        tc,
+        |key, buf| kw.wrap_key(key, buf).map(<[u8]>::len),
+        |wkey, buf| kw.unwrap_key(wkey, buf).map(<[u8]>::len),
+    );
+}
+
+fn run_kwp<C>(tc: &TestCase)
+where
+    C: KeyInit + BlockCipherEncrypt<BlockSize = U16> + BlockCipherDecrypt<BlockSize = U16>,
+{
+    let kwp = AesKwp::<C>::try_from(&decode_hex(&tc.key)[..]).unwrap();
+    run_test(
+        tc,
+        |key, buf| kwp.wrap_key(key, buf).map(<[u8]>::len),
+        |wkey, buf| kwp.unwrap_key(wkey, buf).map(<[u8]>::len),
+    );
+}
+
+/// Run all test cases in `data` and check that all of them were executed.
+fn run_file(data: &str, padded: bool) {
+    let file: TestFile = serde_json::from_str(data).unwrap();
+    let mut count = 0;
+    for group in &file.test_groups {
+        for tc in &group.tests {
+            match (group.key_size, padded) {
+                (128, false) => run_kw::<aes_kw::aes::Aes128>(tc),
+                (192, false) => run_kw::<aes_kw::aes::Aes192>(tc),
+                (256, false) => run_kw::<aes_kw::aes::Aes256>(tc),
+                (128, true) => run_kwp::<aes_kw::aes::Aes128>(tc),
+                (192, true) => run_kwp::<aes_kw::aes::Aes192>(tc),
+                (256, true) => run_kwp::<aes_kw::aes::Aes256>(tc),
+                (size, _) => panic!("unsupported key size: {size}"),
+            }
+            count += 1;
+        }
+    }
+    assert_eq!(count, file.number_of_tests);
+}
+
+#[test]
+fn wycheproof_aes_wrap() {
+    run_file(include_str!("data/wycheproof/aes_wrap_test.json"), false);
+}
+
+#[test]
+fn wycheproof_aes_kwp() {
+    run_file(include_str!("data/wycheproof/aes_kwp_test.json"), true);
+}
@@ -8,19 +8,43 @@
 #![forbid(unsafe_code)]
 #![warn(missing_docs, rust_2018_idioms)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 use belt_block::{
     belt_wblock_dec, belt_wblock_enc,
     cipher::{
         array::ArraySize,
-        consts::U16,
-        typenum::{GrEq, IsGreaterOrEqual, NonZero, Sum, Unsigned},
+        consts::{B1, U16},
+        typenum::{GrEq, IsGreaterOrEqual, Sum, Unsigned},
         Array,
     },
     BeltBlock,
 };
 use core::{fmt, ops::Add};
 
-pub use belt_block::cipher::{self, Key, KeyInit, KeySizeUser};
+pub use belt_block::cipher::{self, BlockSizeUser, Key, KeyInit, KeySizeUser};
+#[cfg(feature = "oid")]
+pub use const_oid;
+pub use key_wrap_traits::{
+    self, Error, IntegrityCheckFailed, KeyWrap, KeyWrapDecrypt, KeyWrapEncrypt,
+};
+#[cfg(feature = "rand_core")]
+pub use rand_core;
+
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+#[cfg(feature = "zeroize")]
+use belt_block::cipher::zeroize::Zeroize;
+#[cfg(feature = "oid")]
+use const_oid::{AssociatedOid, ObjectIdentifier};
+#[cfg(feature = "rand_core")]
+use rand_core::CryptoRngCore;
+
+/// Result type with the `belt-kwp` crate's [`Error`] type.
+pub type Result<T> = core::result::Result<T, Error>;
 
 /// Size of wrapping "header" represented as a `typenum` type.
 pub type IvLen = U16;
@@ -28,6 +52,59 @@ pub type IvLen = U16;
 pub type WrappedKey<N> = Array<u8, Sum<N, IvLen>>;
 /// Size of wrapping "header".
 pub const IV_LEN: usize = IvLen::USIZE;
+/// Default all-zero "header" used by [`BeltKwp::wrap_key_default`] and
+/// [`BeltKwp::unwrap_key_default`].
+pub const DEFAULT_IV: [u8; IV_LEN] = [0u8; IV_LEN];
+
+/// Iterate over [`IV_LEN`] bytes long blocks of `data`.
+///
+/// `data` can be either wrapped or plaintext data. Returns [`Error::NotSemiblockAligned`]
+/// if length of `data` is not multiple of [`IV_LEN`].
+///
+/// Note that `belt-wblock` processes data of any length, so wrapped keys
+/// are not necessarily aligned to the block size.
+#[inline]
+pub fn semiblocks(data: &[u8]) -> Result<impl Iterator<Item = &[u8; IV_LEN]>> {
+    if data.len() % IV_LEN != 0 {
+        return Err(Error::NotSemiblockAligned);
+    }
+    Ok(data
+        .chunks_exact(IV_LEN)
+        .map(|chunk| chunk.try_into().unwrap()))
+}
+
+/// Key sizes supported by the fixed-size methods of [`BeltKwp`].
+///
+/// `belt-wblock` processes at least two blocks, i.e. a key together with
+/// the [`IV_LEN`] bytes long header must be at least 32 bytes. This trait
+/// is implemented for all [`ArraySize`]s bigger or equal to [`IvLen`] (`U16`)
+/// and can not be implemented outside of this crate.
+pub trait BeltKwInput: sealed::Sealed + ArraySize + Add<IvLen, Output: ArraySize> {}
+
+impl<N> BeltKwInput for N
+where
+    N: ArraySize + Add<IvLen, Output: ArraySize> + IsGreaterOrEqual<IvLen>,
+    GrEq<N, IvLen>: sealed::MinKeyLen,
+{
+}
+
+mod sealed {
+    use super::{ArraySize, B1};
+
+    pub trait Sealed {}
+
+    impl<N: ArraySize> Sealed for N {}
+
+    /// Marker implemented only for the result of the `N >= IvLen` comparison
+    /// which is true, used to produce a readable error for short keys.
+    #[diagnostic::on_unimplemented(
+        message = "BelT key-wrap input must be at least 16 bytes",
+        label = "key size is smaller than 16 bytes"
+    )]
+    pub trait MinKeyLen {}
+
+    impl MinKeyLen for B1 {}
+}
 
 /// BelT Key Wrap instance as defined in STB 34.101.34-2020.
 #[derive(Clone, Copy, PartialEq)]
@@ -42,37 +119,219 @@ impl fmt::Debug for BeltKwp {
 }
 
 impl BeltKwp {
-    /// Wrap key `x` with given `iv` and write result to `out`.
+    /// Length of the "header" block in bytes.
+    pub const SEMIBLOCK_LEN: usize = IV_LEN;
+    /// Number of bytes added to a key by wrapping.
+    pub const OVERHEAD: usize = IV_LEN;
+    /// Minimum supported length of a key in bytes.
+    pub const MIN_KEY_LEN: usize = Self::MIN_DATA_LEN;
+    /// Minimum supported length of wrapped data in bytes.
+    pub const MIN_DATA_LEN: usize = 16;
+
+    /// Get length of the data unwrapped from wrapped data of length `wrapped_len`,
+    /// i.e. length of the slice returned by [`BeltKwp::unwrap_key`].
     ///
-    /// Size of `x` must be bigger than 16 bytes.
-    /// Size of `out` must be bigger or equal to x.len() + [IV_LEN].
+    /// Returns [`Error::InvalidDataSize`] if `wrapped_len` is smaller than
+    /// [`BeltKwp::MIN_DATA_LEN`] + [`IV_LEN`].
     #[inline]
-    pub fn wrap_key<'a>(
+    pub fn unwrap_key_len(wrapped_len: usize) -> Result<usize> {
+        if wrapped_len < Self::MIN_DATA_LEN + Self::OVERHEAD {
+            return Err(Error::InvalidDataSize);
+        }
+        Ok(wrapped_len - IV_LEN)
+    }
+
+    /// Wrap arbitrary `data` with given `iv` and write result to `out`.
+    ///
+    /// STB 34.101.31-2020 defines the wrapping for any data, not just keys,
+    /// so this method can be used to protect small blobs like tokens.
+    /// Wrapping provides both confidentiality and integrity of `data`,
+    /// integrity is checked by [`BeltKwp::unwrap_data`] using `iv`.
+    ///
+    /// Size of `data` must be bigger or equal to [`BeltKwp::MIN_DATA_LEN`].
+    /// Size of `out` must be bigger or equal to data.len() + [IV_LEN].
+    pub fn wrap_data<'a>(
         &self,
-        x: &[u8],
+        data: &[u8],
         iv: &[u8; IV_LEN],
         out: &'a mut [u8],
-    ) -> Result<&'a [u8], Error> {
-        if x.len() < 16 {
+    ) -> Result<&'a [u8]> {
+        // `belt_wblock_enc` processes at least 32 bytes, i.e. `data || iv`
+        // with 16 bytes of data is the shortest supported input
+        if data.len() < Self::MIN_DATA_LEN {
             return Err(Error::InvalidDataSize);
         }
 
-        let out_len = x.len() + IV_LEN;
+        let out_len = data.len() + IV_LEN;
         if out.len() < out_len {
             return Err(Error::InvalidOutputSize {
-                expected: x.len() + IV_LEN,
+                expected_len: out_len,
             });
         }
         let out = &mut out[..out_len];
 
-        let (l, r) = out.split_at_mut(x.len());
-        l.copy_from_slice(x);
+        let (l, r) = out.split_at_mut(data.len());
+        l.copy_from_slice(data);
         r.copy_from_slice(iv);
 
         belt_wblock_enc(out, &self.key).map_err(|_| Error::InvalidDataSize)?;
         Ok(out)
     }
 
+    /// Unwrap data in `y` wrapped by [`BeltKwp::wrap_data`] with given `iv`
+    /// and write result to `out`.
+    ///
+    /// Size of wrapped data `y` must be bigger or equal to
+    /// [`BeltKwp::MIN_DATA_LEN`] + [IV_LEN].
+    /// Size of `out` must be bigger or equal to the size of `y`.
+    ///
+    /// Only the first `y.len()` bytes of `out` are used as a scratch space,
+    /// bytes beyond them are left untouched. If unwrapping fails after
+    /// the scratch space was written, it gets zeroized before returning.
+    pub fn unwrap_data<'a>(
+        &self,
+        y: &[u8],
+        iv: &[u8; IV_LEN],
+        out: &'a mut [u8],
+    ) -> Result<&'a [u8]> {
+        let data_len = Self::unwrap_key_len(y.len())?;
+
+        if out.len() < y.len() {
+            return Err(Error::InvalidOutputSize {
+                expected_len: y.len(),
+            });
+        }
+
+        let out = &mut out[..y.len()];
+        out.copy_from_slice(y);
+
+        if belt_wblock_dec(out, &self.key).is_err() {
+            out.fill(0);
+            return Err(Error::InvalidDataSize);
+        }
+
+        let calc_iv = u128::from_ne_bytes(out[data_len..].try_into().unwrap());
+        let expected_iv = u128::from_ne_bytes(*iv);
+        // We expect that comparison of `u128`s will be constant-time
+        if calc_iv != expected_iv {
+            out.fill(0);
+            return Err(Error::IntegrityCheckFailed);
+        }
+
+        Ok(&out[..data_len])
+    }
+
+    /// Wrap key `x` with given `iv` and write result to `out`.
+    ///
+    /// Size of `x` must be bigger or equal to 16 bytes.
+    /// Size of `out` must be bigger or equal to x.len() + [IV_LEN].
+    #[inline]
+    pub fn wrap_key<'a>(&self, x: &[u8], iv: &[u8; IV_LEN], out: &'a mut [u8]) -> Result<&'a [u8]> {
+        self.wrap_data(x, iv, out)
+    }
+
+    /// Wrap key `x` with given `iv` and write result to `out`.
+    ///
+    /// Same as [`BeltKwp::wrap_key`], but accepts any type which can be
+    /// referenced as a byte slice, e.g. `Vec<u8>` or `[u8; N]`.
+    #[inline]
+    pub fn wrap_key_ref<'a, K: AsRef<[u8]>>(
+        &self,
+        x: K,
+        iv: &[u8; IV_LEN],
+        out: &'a mut [u8],
+    ) -> Result<&'a [u8]> {
+        self.wrap_key(x.as_ref(), iv, out)
+    }
+
+    /// Wrap key `x` with given `iv` and write result to `out`, then zeroize `x`.
+    ///
+    /// Same as [`BeltKwp::wrap_key`], but wipes the caller's plaintext buffer
+    /// after successful wrapping. Note that this method mutates the input.
+    /// On error `x` is left untouched.
+    #[cfg(feature = "zeroize")]
+    #[inline]
+    pub fn wrap_key_consuming<'a>(
+        &self,
+        x: &mut [u8],
+        iv: &[u8; IV_LEN],
+        out: &'a mut [u8],
+    ) -> Result<&'a [u8]> {
+        let res = self.wrap_key(x, iv, out)?;
+        x.zeroize();
+        Ok(res)
+    }
+
+    /// Wrap key `x` with given `iv` and write result to `out`.
+    ///
+    /// Returns number of bytes written to `out`.
+    ///
+    /// Size requirements for `x` and `out` are the same as in [`BeltKwp::wrap_key`].
+    #[inline]
+    pub fn wrap_key_into(&self, x: &[u8], iv: &[u8; IV_LEN], out: &mut [u8]) -> Result<usize> {
+        self.wrap_data(x, iv, out).map(<[u8]>::len)
+    }
+
+    /// Wrap key `x` with a random IV generated using `rng` and write result to `out`.
+    ///
+    /// Returns the generated IV together with the wrapped key. The IV is not
+    /// secret, but it must be stored alongside the wrapped key since it's
+    /// required by [`BeltKwp::unwrap_key`].
+    ///
+    /// Size requirements for `x` and `out` are the same as in [`BeltKwp::wrap_key`].
+    #[cfg(feature = "rand_core")]
+    #[inline]
+    pub fn wrap_key_with_rng<'a, R: CryptoRngCore + ?Sized>(
+        &self,
+        rng: &mut R,
+        x: &[u8],
+        out: &'a mut [u8],
+    ) -> Result<([u8; IV_LEN], &'a [u8])> {
+        let mut iv = [0u8; IV_LEN];
+        rng.fill_bytes(&mut iv);
+        let res = self.wrap_key(x, &iv, out)?;
+        Ok((iv, res))
+    }
+
+    /// Wrap key `x` with given `iv` and write `iv` followed by the wrapped key to `out`.
+    ///
+    /// This is a convenience method for storing the header together with
+    /// the wrapped key, e.g. when `iv` is random. The result can be unwrapped
+    /// using [`BeltKwp::unwrap_key_combined`].
+    ///
+    /// Size of `x` must be bigger or equal to 16 bytes.
+    /// Size of `out` must be bigger or equal to x.len() + 2 * [IV_LEN].
+    #[inline]
+    pub fn wrap_key_combined<'a>(
+        &self,
+        x: &[u8],
+        iv: &[u8; IV_LEN],
+        out: &'a mut [u8],
+    ) -> Result<&'a [u8]> {
+        let out_len = x.len() + 2 * IV_LEN;
+        if out.len() < out_len {
+            return Err(Error::InvalidOutputSize {
+                expected_len: out_len,
+            });
+        }
+        let (header, y) = out.split_at_mut(IV_LEN);
+        let y_len = self.wrap_key(x, iv, y)?.len();
+        header.copy_from_slice(iv);
+        Ok(&out[..IV_LEN + y_len])
+    }
+
+    /// Wrap key `x` with [`DEFAULT_IV`] and write result to `out`.
+    ///
+    /// With a fixed header the wrapping provides only integrity protection
+    /// of the key data, i.e. equal keys result in equal wrapped keys. Use this
+    /// method only if this is acceptable for your use case.
+    ///
+    /// Size requirements for `x` and `out` are the same as in [`BeltKwp::wrap_key`].
+    #[inline]
+    pub fn wrap_key_default<'a>(&self, x: &[u8], out: &'a mut [u8]) -> Result<&'a [u8]> {
+        self.wrap_key(x, &DEFAULT_IV, out)
+    }
+
     /// Wrap fixed-size key `x` with given `iv` and return resulting array.
     ///
     /// This method is roughly equivalent to:
@@ -89,12 +348,16 @@ impl BeltKwp {
     /// ```
     /// but uses [`hybrid_array::Array`][Array] instead of built-in arrays
     /// to work around current limitations of the const generics system.
+    /// Built-in arrays can be converted to and from [`Array`] using
+    /// the [`From`]/[`Into`] traits.
+    ///
+    /// Key size `N` must be at least 16 bytes, which is enforced by
+    /// the [`BeltKwInput`] bound at compile time.
     #[inline]
+    #[must_use]
     pub fn wrap_fixed_key<N>(&self, x: &Array<u8, N>, iv: &[u8; IV_LEN]) -> WrappedKey<N>
     where
-        N: ArraySize + Add<IvLen> + IsGreaterOrEqual<IvLen>,
-        Sum<N, IvLen>: ArraySize,
-        GrEq<N, IvLen>: NonZero,
+        N: BeltKwInput,
     {
         let mut res = WrappedKey::<N>::default();
         let (l, r) = res.split_at_mut(x.len());
@@ -107,40 +370,77 @@ impl BeltKwp {
 
     /// Unwrap key in `y` with given `iv` and write result to `out`.
     ///
+    /// The header `iv` is encrypted together with the key, so it can not be
+    /// recovered from `y` alone. Instead the caller must provide the same `iv`
+    /// which was used for wrapping, and the decrypted header gets compared
+    /// against it as an integrity check. If the header is stored together with
+    /// the wrapped key, consider using [`BeltKwp::unwrap_key_combined`].
+    ///
     /// Size of wrapped data `y` must be bigger or equal to 32 bytes.
     /// Size of `out` must be bigger or equal to the size of `y`.
+    ///
+    /// Only the first `y.len()` bytes of `out` are used as a scratch space,
+    /// bytes beyond them are left untouched. If unwrapping fails after
+    /// the scratch space was written, it gets zeroized before returning.
+    ///
+    /// The returned slice is truncated to the unwrapped key, i.e. its length is
+    /// equal to [`BeltKwp::unwrap_key_len`] of `y.len()`. The following [`IV_LEN`]
+    /// bytes of `out` contain the decrypted header, which is equal to `iv`.
     #[inline]
     pub fn unwrap_key<'a>(
         &self,
         y: &[u8],
         iv: &[u8; IV_LEN],
         out: &'a mut [u8],
-    ) -> Result<&'a [u8], Error> {
-        if y.len() < 32 {
-            return Err(Error::InvalidDataSize);
-        }
+    ) -> Result<&'a [u8]> {
+        self.unwrap_data(y, iv, out)
+    }
 
-        if out.len() < y.len() {
-            return Err(Error::InvalidOutputSize { expected: y.len() });
+    /// Unwrap key in `blob` produced by [`BeltKwp::wrap_key_combined`]
+    /// and write result to `out`.
+    ///
+    /// The first [`IV_LEN`] bytes of `blob` are used as the expected header,
+    /// while the rest is passed to [`BeltKwp::unwrap_key`]. Note that since
+    /// the header is stored in clear, it only protects against accidental
+    /// corruption and mixing up wrapped keys, not against a deliberate
+    /// substitution of the whole blob.
+    ///
+    /// Size of `blob` must be bigger or equal to 48 bytes.
+    /// Size of `out` must be bigger or equal to `blob.len() - IV_LEN`.
+    #[inline]
+    pub fn unwrap_key_combined<'a>(&self, blob: &[u8], out: &'a mut [u8]) -> Result<&'a [u8]> {
+        if blob.len() < IV_LEN {
+            return Err(Error::InvalidDataSize);
         }
+        let (iv, y) = blob.split_at(IV_LEN);
+        self.unwrap_key(y, iv.try_into().unwrap(), out)
+    }
 
-        let out = &mut out[..y.len()];
-        out.copy_from_slice(y);
-
-        belt_wblock_dec(out, &self.key).map_err(|_| Error::InvalidDataSize)?;
-
-        let (key, rem) = out.split_at_mut(y.len() - IV_LEN);
+    /// Unwrap key in `y` with given `iv` into `scratch` and pass the unwrapped key to `f`.
+    ///
+    /// `scratch` is zeroed after `f` returns, so the unwrapped key does not outlive
+    /// the call. `f` is called only if the integrity check passes.
+    ///
+    /// Size requirements for `y` and `scratch` are the same as in [`BeltKwp::unwrap_key`].
+    #[inline]
+    pub fn unwrap_key_with<R, F: FnOnce(&[u8]) -> R>(
+        &self,
+        y: &[u8],
+        iv: &[u8; IV_LEN],
+        scratch: &mut [u8],
+        f: F,
+    ) -> Result<R> {
+        let res = self.unwrap_key(y, iv, scratch).map(f);
+        scratch.fill(0);
+        res
+    }
 
-        let calc_iv = u128::from_ne_bytes(rem.try_into().unwrap());
-        let expected_iv = u128::from_ne_bytes(*iv);
-        // We expect that comparison of `u128`s will be constant-time
-        if calc_iv == expected_iv {
-            Ok(key)
-        } else {
-            key.fill(0);
-            rem.fill(0);
-            Err(Error::IntegrityCheckFailed)
-        }
+    /// Unwrap key in `y` wrapped with [`DEFAULT_IV`] and write result to `out`.
+    ///
+    /// Size requirements for `y` and `out` are the same as in [`BeltKwp::unwrap_key`].
+    #[inline]
+    pub fn unwrap_key_default<'a>(&self, y: &[u8], out: &'a mut [u8]) -> Result<&'a [u8]> {
+        self.unwrap_key(y, &DEFAULT_IV, out)
     }
 
     /// Unwrap key in `y` with given `iv` and return resulting key.
@@ -159,35 +459,113 @@ impl BeltKwp {
     /// ```
     /// but uses [`hybrid_array::Array`][Array] instead of built-in arrays
     /// to work around current limitations of the const generics system.
+    /// Built-in arrays can be converted to and from [`Array`] using
+    /// the [`From`]/[`Into`] traits.
     #[inline]
     pub fn unwrap_fixed_key<N>(
         &self,
         y: &WrappedKey<N>,
         iv: &[u8; IV_LEN],
-    ) -> Result<Array<u8, N>, IntegrityCheckFailed>
+    ) -> core::result::Result<Array<u8, N>, IntegrityCheckFailed>
     where
-        N: ArraySize + Add<IvLen> + IsGreaterOrEqual<IvLen>,
-        Sum<N, IvLen>: ArraySize,
-        GrEq<N, IvLen>: NonZero,
+        N: BeltKwInput,
     {
         let mut y = y.clone();
+        let res = self.unwrap_fixed_key_in_place(&mut y, iv).cloned();
+        y.fill(0);
+        res
+    }
 
-        belt_wblock_dec(&mut y, &self.key).expect("y has correct size");
+    /// Unwrap key in `y` with given `iv` in place and return reference to
+    /// the resulting key.
+    ///
+    /// Same as [`BeltKwp::unwrap_fixed_key`], but does not copy `y`, which
+    /// reduces stack usage for big keys. The resulting key is stored
+    /// in the first `N` bytes of `y`. On integrity check failure `y` is zeroed.
+    #[inline]
+    pub fn unwrap_fixed_key_in_place<'a, N>(
+        &self,
+        y: &'a mut WrappedKey<N>,
+        iv: &[u8; IV_LEN],
+    ) -> core::result::Result<&'a Array<u8, N>, IntegrityCheckFailed>
+    where
+        N: BeltKwInput,
+    {
+        belt_wblock_dec(y, &self.key).expect("y has correct size");
 
         // We could've used `Array:split`, but it's easier to do it this way
-        let (key, rem) = y.split_at(N::USIZE);
+        let (key, rem) = y.split_at_mut(N::USIZE);
 
-        let calc_iv = u128::from_ne_bytes(rem.try_into().unwrap());
+        let calc_iv = u128::from_ne_bytes((&*rem).try_into().unwrap());
         let expected_iv = u128::from_ne_bytes(*iv);
         // We expect that comparison of `u128`s will be constant-time
         if calc_iv == expected_iv {
-            Ok(key.try_into().unwrap())
+            Ok((&*key).try_into().unwrap())
         } else {
+            key.fill(0);
+            rem.fill(0);
             Err(IntegrityCheckFailed)
         }
     }
 }
 
+#[cfg(feature = "alloc")]
+impl BeltKwp {
+    /// Wrap key `x` with given `iv` and return wrapped key.
+    ///
+    /// Size requirements for `x` are the same as in [`BeltKwp::wrap_key`].
+    pub fn wrap_key_vec(&self, x: &[u8], iv: &[u8; IV_LEN]) -> Result<Vec<u8>> {
+        let mut out = vec![0u8; x.len() + IV_LEN];
+        self.wrap_key_into(x, iv, &mut out)?;
+        Ok(out)
+    }
+
+    /// Wrap key `x` with given `iv` and append the wrapped key to `out`.
+    ///
+    /// `out` is extended by `x.len() + IV_LEN` bytes and the key is wrapped
+    /// directly in the new tail. On error `out` is left unchanged.
+    ///
+    /// Size requirements for `x` are the same as in [`BeltKwp::wrap_key`].
+    pub fn wrap_key_append(&self, x: &[u8], iv: &[u8; IV_LEN], out: &mut Vec<u8>) -> Result<()> {
+        let start = out.len();
+        out.resize(start + x.len() + IV_LEN, 0);
+        let res = self.wrap_key_into(x, iv, &mut out[start..]);
+        if res.is_err() {
+            out.truncate(start);
+        }
+        res.map(|_| ())
+    }
+
+    /// Unwrap key in `y` with given `iv` and return unwrapped key.
+    ///
+    /// Size requirements for `y` are the same as in [`BeltKwp::unwrap_key`].
+    pub fn unwrap_key_vec(&self, y: &[u8], iv: &[u8; IV_LEN]) -> Result<Vec<u8>> {
+        let mut out = vec![0u8; y.len()];
+        let key_len = self.unwrap_key(y, iv, &mut out)?.len();
+        out.truncate(key_len);
+        Ok(out)
+    }
+
+    /// Check integrity of `y` wrapped with given `iv` without exposing
+    /// the unwrapped key.
+    ///
+    /// The key is unwrapped into an internal buffer, which gets zeroed
+    /// before returning.
+    ///
+    /// Size requirements for `y` are the same as in [`BeltKwp::unwrap_key`].
+    pub fn verify(&self, y: &[u8], iv: &[u8; IV_LEN]) -> Result<()> {
+        let mut scratch = vec![0u8; y.len()];
+        self.unwrap_key_with(y, iv, &mut scratch, |_| ())
+    }
+}
+
+// Keys of runtime length can be passed to `KeyInit::new_from_slice`,
+// which validates the length before calling `new`.
+//
+// Unlike `AesKw`, `BeltKwp` does not implement `InnerInit` with `Inner = BeltBlock`:
+// `belt_wblock_enc`/`belt_wblock_dec` work with raw key words, which `BeltBlock`
+// does not expose, and a `BeltBlock` instance does not retain the key bytes
+// which could be used to re-derive them.
 impl KeyInit for BeltKwp {
     fn new(key: &Key<Self>) -> Self {
         let mut res = [0u32; 8];
@@ -207,44 +585,75 @@ impl KeySizeUser for BeltKwp {
     }
 }
 
-/// Errors emitted from the wrap and unwrap operations.
-#[derive(Debug)]
-pub enum Error {
-    /// Input data length invalid.
-    InvalidDataSize,
+impl BlockSizeUser for BeltKwp {
+    type BlockSize = <BeltBlock as BlockSizeUser>::BlockSize;
+}
 
-    /// Output buffer size invalid.
-    InvalidOutputSize {
-        /// Expected size in bytes.
-        expected: usize,
-    },
+// `BeltKwp` implements `Copy`, so it can not be zeroized on drop.
+#[cfg(feature = "zeroize")]
+impl Zeroize for BeltKwp {
+    fn zeroize(&mut self) {
+        self.key.zeroize();
+    }
+}
 
-    /// Integrity check did not pass.
-    IntegrityCheckFailed,
+/// `belt-keywrap256` OID as defined in STB 34.101.31-2020.
+#[cfg(feature = "oid")]
+impl AssociatedOid for BeltKwp {
+    const OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.112.0.2.0.34.101.31.73");
 }
 
-impl fmt::Display for Error {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Error::InvalidDataSize => f.write_str("invalid data size"),
-            Error::InvalidOutputSize { expected } => {
-                write!(f, "invalid output buffer size: expected {expected}")
-            }
-            Error::IntegrityCheckFailed => f.write_str("integrity check failed"),
+impl KeyWrapEncrypt for BeltKwp {
+    fn iv_len(&self) -> usize {
+        IV_LEN
+    }
+
+    fn wrap_len(&self, key_len: usize) -> Result<usize> {
+        if key_len < BeltKwp::MIN_KEY_LEN {
+            return Err(Error::InvalidDataSize);
         }
+        key_len.checked_add(IV_LEN).ok_or(Error::LengthOverflow)
+    }
+
+    fn wrap_key_default<'a>(&self, key: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8]> {
+        BeltKwp::wrap_key(self, key, &DEFAULT_IV, buf)
+    }
+
+    fn wrap_key_with_iv<'a>(&self, key: &[u8], iv: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8]> {
+        BeltKwp::wrap_key(self, key, fixed_iv(iv)?, buf)
     }
 }
 
-impl core::error::Error for Error {}
+impl KeyWrapDecrypt for BeltKwp {
+    fn iv_len(&self) -> usize {
+        IV_LEN
+    }
 
-/// Error that indicates integrity check failure.
-#[derive(Clone, Copy, Debug)]
-pub struct IntegrityCheckFailed;
+    fn unwrap_len(&self, wkey_len: usize) -> Result<usize> {
+        // the whole wrapped key is used as a scratch space
+        if wkey_len < BeltKwp::MIN_KEY_LEN + IV_LEN {
+            return Err(Error::InvalidDataSize);
+        }
+        Ok(wkey_len)
+    }
 
-impl fmt::Display for IntegrityCheckFailed {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str("integrity check failed")
+    fn unwrap_key_default<'a>(&self, wkey: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8]> {
+        BeltKwp::unwrap_key(self, wkey, &DEFAULT_IV, buf)
+    }
+
+    fn unwrap_key_with_iv<'a>(
+        &self,
+        wkey: &[u8],
+        iv: &[u8],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8]> {
+        BeltKwp::unwrap_key(self, wkey, fixed_iv(iv)?, buf)
     }
 }
 
-impl core::error::Error for IntegrityCheckFailed {}
+/// Get IV from `iv` or return [`Error::InvalidIvSize`].
+fn fixed_iv(iv: &[u8]) -> Result<&[u8; IV_LEN]> {
+    iv.try_into().map_err(|_| Error::InvalidIvSize {
+        expected_len: IV_LEN,
+    })
+}
@@ -1,6 +1,8 @@
 //! Test vectors from STB 4.101.31-2020 (section A.10, tables A.21-A.22):
 //! https://apmi.bsu.by/assets/files/std/belt-spec371.pdf
-use belt_kwp::{cipher::consts::U32, BeltKwp, KeyInit};
+use belt_kwp::{
+    cipher::consts::U32, BeltKwp, BlockSizeUser, Error, IntegrityCheckFailed, KeyInit, DEFAULT_IV,
+};
 use hex_literal::hex;
 
 #[test]
@@ -32,9 +34,9 @@ fn belt_kwp() {
     assert_eq!(x1, res);
 
     let res = kw.wrap_fixed_key::<U32>(&x1.into(), &i1);
-    assert_eq!(y1, res.0);
+    assert_eq!(y1, res);
     let res = kw.unwrap_fixed_key::<U32>(&res, &i1).unwrap();
-    assert_eq!(x1, res.0);
+    assert_eq!(x1, res);
 
     let kw = BeltKwp::new(&k2.into());
 
@@ -44,7 +46,472 @@ fn belt_kwp() {
     assert_eq!(x2, res);
 
     let res = kw.wrap_fixed_key::<U32>(&x2.into(), &i2);
-    assert_eq!(y2, res.0);
+    assert_eq!(y2, res);
     let res = kw.unwrap_fixed_key::<U32>(&res, &i2).unwrap();
-    assert_eq!(x2, res.0);
+    assert_eq!(x2, res);
+
+    let mut y = y2.into();
+    let res = kw.unwrap_fixed_key_in_place::<U32>(&mut y, &i2).unwrap();
+    assert_eq!(x2, *res);
+
+    let mut y = y2.into();
+    let res = kw.unwrap_fixed_key_in_place::<U32>(&mut y, &i1);
+    assert_eq!(res, Err(IntegrityCheckFailed));
+    assert_eq!(y, [0u8; 48]);
+}
+
+#[test]
+fn belt_kwp_short_key() {
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let x = hex!("B194BAC8 0A08F53B 366D008E 584A5DE4");
+    let i = hex!("5BE3D612 17B96181 FE6786AD 716B890B");
+
+    let kw = BeltKwp::new(&k.into());
+
+    // 16 bytes is the smallest supported key size
+    let mut buf = [0u8; 32];
+    let y: [u8; 32] = kw.wrap_key(&x, &i, &mut buf).unwrap().try_into().unwrap();
+    let res = kw.unwrap_key(&y, &i, &mut buf).unwrap();
+    assert_eq!(x, res);
+
+    let res = kw.wrap_key(&x[..15], &i, &mut buf);
+    assert_eq!(res, Err(Error::InvalidDataSize));
+    let res = kw.unwrap_key(&y[..31], &i, &mut buf);
+    assert_eq!(res, Err(Error::InvalidDataSize));
+}
+
+#[test]
+fn belt_kwp_unwrap_failure_wipes_output() {
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let i = hex!("5BE3D612 17B96181 FE6786AD 716B890B");
+    let y = hex!(
+        "49A38EE1 08D6C742 E52B774F 00A6EF98 B106CBD1 3EA4FB06 80323051 BC04DF76"
+        "E487B055 C69BCF54 1176169F 1DC9F6C8"
+    );
+
+    let kw = BeltKwp::new(&k.into());
+
+    let mut buf = [0xFFu8; 64];
+    let mut bad_i = i;
+    bad_i[0] ^= 1;
+    let res = kw.unwrap_key(&y, &bad_i, &mut buf);
+    assert_eq!(res, Err(Error::IntegrityCheckFailed));
+    assert_eq!(buf[..48], [0u8; 48]);
+    assert_eq!(buf[48..], [0xFFu8; 16]);
+}
+
+#[test]
+fn belt_kwp_invalid_output_size() {
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let x = hex!("B194BAC8 0A08F53B 366D008E 584A5DE4 8504FA9D 1BB6C7AC 252E72C2 02FDCE0D");
+    let i = hex!("5BE3D612 17B96181 FE6786AD 716B890B");
+    let y = hex!(
+        "49A38EE1 08D6C742 E52B774F 00A6EF98 B106CBD1 3EA4FB06 80323051 BC04DF76"
+        "E487B055 C69BCF54 1176169F 1DC9F6C8"
+    );
+
+    let kw = BeltKwp::new(&k.into());
+
+    let mut buf = [0u8; 47];
+    let res = kw.wrap_key(&x, &i, &mut buf);
+    assert_eq!(res, Err(Error::InvalidOutputSize { expected_len: 48 }));
+    let res = kw.unwrap_key(&y, &i, &mut buf);
+    assert_eq!(res, Err(Error::InvalidOutputSize { expected_len: 48 }));
+}
+
+#[test]
+#[cfg(feature = "rand_core")]
+fn belt_kwp_wrap_with_rng() {
+    use belt_kwp::rand_core::{impls, CryptoRng, RngCore};
+
+    struct CounterRng(u64);
+
+    impl RngCore for CounterRng {
+        fn next_u32(&mut self) -> u32 {
+            self.next_u64() as u32
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            self.0 += 1;
+            self.0
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            impls::fill_bytes_via_next(self, dest)
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), belt_kwp::rand_core::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl CryptoRng for CounterRng {}
+
+    let x = hex!("B194BAC8 0A08F53B 366D008E 584A5DE4 8504FA9D 1BB6C7AC 252E72C2 02FDCE0D");
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let kw = BeltKwp::new(&k.into());
+    let mut rng = CounterRng(0);
+
+    let mut buf1 = [0u8; 48];
+    let (iv1, y1) = kw.wrap_key_with_rng(&mut rng, &x, &mut buf1).unwrap();
+    let mut buf2 = [0u8; 48];
+    let (iv2, y2) = kw.wrap_key_with_rng(&mut rng, &x, &mut buf2).unwrap();
+    assert_ne!(iv1, iv2);
+    assert_ne!(y1, y2);
+
+    let mut buf = [0u8; 48];
+    let mut expected = [0u8; 48];
+    let res = kw.wrap_key(&x, &iv1, &mut expected).unwrap();
+    assert_eq!(y1, res);
+    assert_eq!(kw.unwrap_key(y1, &iv1, &mut buf).unwrap(), x);
+    assert_eq!(kw.unwrap_key(y2, &iv2, &mut buf).unwrap(), x);
+
+    let res = kw.wrap_key_with_rng(&mut rng, &x[..15], &mut buf);
+    assert_eq!(res, Err(Error::InvalidDataSize));
+}
+
+#[test]
+fn belt_kwp_wrap_key_into() {
+    let x = hex!("B194BAC8 0A08F53B 366D008E 584A5DE4 8504FA9D 1BB6C7AC 252E72C2 02FDCE0D");
+    let iv = hex!("5BE3D612 17B96181 FE6786AD 716B890B");
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let y = hex!(
+        "49A38EE1 08D6C742 E52B774F 00A6EF98 B106CBD1 3EA4FB06 80323051 BC04DF76"
+        "E487B055 C69BCF54 1176169F 1DC9F6C8"
+    );
+    let kw = BeltKwp::new(&k.into());
+
+    let mut buf = [0u8; 64];
+    let n = kw.wrap_key_into(&x, &iv, &mut buf).unwrap();
+    assert_eq!(n, y.len());
+    assert_eq!(buf[..n], y);
+}
+
+#[test]
+fn belt_kwp_integrity_check_failed_into_error() {
+    fn unwrap(kw: &BeltKwp, y: &[u8; 48], iv: &[u8; 16]) -> Result<[u8; 32], Error> {
+        let x = kw.unwrap_fixed_key::<U32>(y.into(), iv)?;
+        Ok(x.into())
+    }
+
+    let x = hex!("B194BAC8 0A08F53B 366D008E 584A5DE4 8504FA9D 1BB6C7AC 252E72C2 02FDCE0D");
+    let iv = hex!("5BE3D612 17B96181 FE6786AD 716B890B");
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let mut y = hex!(
+        "49A38EE1 08D6C742 E52B774F 00A6EF98 B106CBD1 3EA4FB06 80323051 BC04DF76"
+        "E487B055 C69BCF54 1176169F 1DC9F6C8"
+    );
+    let kw = BeltKwp::new(&k.into());
+
+    assert_eq!(unwrap(&kw, &y, &iv), Ok(x));
+    y[0] ^= 1;
+    assert_eq!(unwrap(&kw, &y, &iv), Err(Error::IntegrityCheckFailed));
+}
+
+#[test]
+fn belt_kwp_new_from_slice() {
+    let x = hex!("B194BAC8 0A08F53B 366D008E 584A5DE4 8504FA9D 1BB6C7AC 252E72C2 02FDCE0D");
+    let iv = hex!("5BE3D612 17B96181 FE6786AD 716B890B");
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let y = hex!(
+        "49A38EE1 08D6C742 E52B774F 00A6EF98 B106CBD1 3EA4FB06 80323051 BC04DF76"
+        "E487B055 C69BCF54 1176169F 1DC9F6C8"
+    );
+
+    let kw = BeltKwp::new_from_slice(&k).unwrap();
+    let mut buf = [0u8; 48];
+    assert_eq!(kw.wrap_key(&x, &iv, &mut buf).unwrap(), y);
+
+    assert!(BeltKwp::new_from_slice(&k[..31]).is_err());
+    assert!(BeltKwp::new_from_slice(&[0u8; 33]).is_err());
+}
+
+#[test]
+fn belt_kwp_wrap_key_ref() {
+    let x = hex!("B194BAC8 0A08F53B 366D008E 584A5DE4 8504FA9D 1BB6C7AC 252E72C2 02FDCE0D");
+    let iv = hex!("5BE3D612 17B96181 FE6786AD 716B890B");
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let y = hex!(
+        "49A38EE1 08D6C742 E52B774F 00A6EF98 B106CBD1 3EA4FB06 80323051 BC04DF76"
+        "E487B055 C69BCF54 1176169F 1DC9F6C8"
+    );
+    let kw = BeltKwp::new(&k.into());
+    let mut buf = [0u8; 48];
+
+    assert_eq!(kw.wrap_key_ref(x, &iv, &mut buf).unwrap(), y);
+    assert_eq!(kw.wrap_key_ref(Vec::from(x), &iv, &mut buf).unwrap(), y);
+}
+
+#[test]
+fn belt_kwp_length_constants() {
+    assert_eq!(BeltKwp::OVERHEAD, 16);
+    assert_eq!(BeltKwp::SEMIBLOCK_LEN, 16);
+    assert_eq!(BeltKwp::block_size(), 16);
+    assert_eq!(BeltKwp::MIN_KEY_LEN, 16);
+
+    let kw = BeltKwp::new(&[0x42; 32].into());
+    let x = [0x42; BeltKwp::MIN_KEY_LEN];
+    let mut buf = [0u8; BeltKwp::MIN_KEY_LEN + BeltKwp::OVERHEAD];
+    assert!(kw.wrap_key(&x, &[0; 16], &mut buf).is_ok());
+    assert_eq!(
+        kw.wrap_key(&x[1..], &[0; 16], &mut buf),
+        Err(Error::InvalidDataSize)
+    );
+}
+
+#[test]
+fn belt_kwp_default_iv() {
+    let x = hex!("B194BAC8 0A08F53B 366D008E 584A5DE4 8504FA9D 1BB6C7AC 252E72C2 02FDCE0D");
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let kw = BeltKwp::new(&k.into());
+
+    let mut buf1 = [0u8; 48];
+    let y = kw.wrap_key_default(&x, &mut buf1).unwrap();
+    let mut buf2 = [0u8; 48];
+    assert_eq!(y, kw.wrap_key(&x, &DEFAULT_IV, &mut buf2).unwrap());
+
+    let mut buf = [0u8; 48];
+    assert_eq!(kw.unwrap_key_default(y, &mut buf).unwrap(), x);
+
+    let iv = hex!("5BE3D612 17B96181 FE6786AD 716B890B");
+    let y = kw.wrap_key(&x, &iv, &mut buf1).unwrap();
+    let res = kw.unwrap_key_default(y, &mut buf);
+    assert_eq!(res, Err(Error::IntegrityCheckFailed));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn belt_kwp_vec() {
+    let x = hex!("B194BAC8 0A08F53B 366D008E 584A5DE4 8504FA9D 1BB6C7AC 252E72C2 02FDCE0D");
+    let i = hex!("5BE3D612 17B96181 FE6786AD 716B890B");
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let y = hex!(
+        "49A38EE1 08D6C742 E52B774F 00A6EF98 B106CBD1 3EA4FB06 80323051 BC04DF76"
+        "E487B055 C69BCF54 1176169F 1DC9F6C8"
+    );
+
+    let kw = BeltKwp::new(&k.into());
+    assert_eq!(kw.wrap_key_vec(&x, &i).unwrap(), y);
+    assert_eq!(kw.unwrap_key_vec(&y, &i).unwrap(), x);
+    assert_eq!(
+        kw.unwrap_key_vec(&y, &DEFAULT_IV),
+        Err(Error::IntegrityCheckFailed)
+    );
+
+    let mut out = b"header".to_vec();
+    kw.wrap_key_append(&x, &i, &mut out).unwrap();
+    assert_eq!(out[..6], *b"header");
+    assert_eq!(out[6..], y);
+    let res = kw.wrap_key_append(&x[..15], &i, &mut out);
+    assert_eq!(res, Err(Error::InvalidDataSize));
+    assert_eq!(out.len(), 6 + y.len());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn belt_kwp_error_into_io_error() {
+    use std::io::ErrorKind;
+
+    let err = std::io::Error::from(Error::IntegrityCheckFailed);
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    let err = std::io::Error::from(Error::InvalidDataSize);
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+#[cfg(feature = "zeroize")]
+fn belt_kwp_zeroize() {
+    use belt_kwp::cipher::zeroize::Zeroize;
+
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let mut kw = BeltKwp::new(&k.into());
+    kw.zeroize();
+    assert_eq!(kw, BeltKwp::new(&Default::default()));
+}
+
+#[test]
+#[cfg(feature = "zeroize")]
+fn belt_kwp_wrap_key_consuming() {
+    let mut x = hex!("B194BAC8 0A08F53B 366D008E 584A5DE4 8504FA9D 1BB6C7AC 252E72C2 02FDCE0D");
+    let i = hex!("5BE3D612 17B96181 FE6786AD 716B890B");
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let y = hex!(
+        "49A38EE1 08D6C742 E52B774F 00A6EF98 B106CBD1 3EA4FB06 80323051 BC04DF76"
+        "E487B055 C69BCF54 1176169F 1DC9F6C8"
+    );
+
+    let kw = BeltKwp::new(&k.into());
+    let mut out = [0u8; 48];
+    let res = kw.wrap_key_consuming(&mut x[..15], &i, &mut out);
+    assert_eq!(res, Err(Error::InvalidDataSize));
+    assert_ne!(x, [0u8; 32]);
+
+    let res = kw.wrap_key_consuming(&mut x, &i, &mut out).unwrap();
+    assert_eq!(res, y);
+    assert_eq!(x, [0u8; 32]);
+}
+
+#[test]
+#[cfg(feature = "oid")]
+fn belt_kwp_oid() {
+    use belt_kwp::const_oid::{AssociatedOid, ObjectIdentifier};
+
+    let oid = ObjectIdentifier::new_unwrap("1.2.112.0.2.0.34.101.31.73");
+    assert_eq!(BeltKwp::OID, oid);
+}
+
+#[test]
+fn belt_kwp_unwrap_key_with() {
+    let x = hex!("B194BAC8 0A08F53B 366D008E 584A5DE4 8504FA9D 1BB6C7AC 252E72C2 02FDCE0D");
+    let i = hex!("5BE3D612 17B96181 FE6786AD 716B890B");
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let y = hex!(
+        "49A38EE1 08D6C742 E52B774F 00A6EF98 B106CBD1 3EA4FB06 80323051 BC04DF76"
+        "E487B055 C69BCF54 1176169F 1DC9F6C8"
+    );
+
+    let kw = BeltKwp::new(&k.into());
+    let mut scratch = [0u8; 48];
+    let res = kw.unwrap_key_with(&y, &i, &mut scratch, |key| key == x);
+    assert_eq!(res, Ok(true));
+    assert_eq!(scratch, [0u8; 48]);
+}
+
+#[test]
+fn belt_kwp_wrap_data() {
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let i = hex!("5BE3D612 17B96181 FE6786AD 716B890B");
+    let data = b"session token: 0123456789";
+
+    let kw = BeltKwp::new(&k.into());
+    let mut buf = [0u8; 64];
+    let y = kw.wrap_data(data, &i, &mut buf).unwrap().to_vec();
+    assert_eq!(y.len(), data.len() + 16);
+    assert_eq!(kw.wrap_key(data, &i, &mut buf).unwrap(), y);
+
+    let res = kw.unwrap_data(&y, &i, &mut buf).unwrap();
+    assert_eq!(res, data);
+
+    let res = kw.unwrap_data(&y, &DEFAULT_IV, &mut buf);
+    assert_eq!(res, Err(Error::IntegrityCheckFailed));
+    assert_eq!(buf[..y.len()], [0u8; 41]);
+
+    let res = kw.wrap_data(&data[..BeltKwp::MIN_DATA_LEN - 1], &i, &mut buf);
+    assert_eq!(res, Err(Error::InvalidDataSize));
+}
+
+#[test]
+fn belt_kwp_combined() {
+    let x = hex!("B194BAC8 0A08F53B 366D008E 584A5DE4 8504FA9D 1BB6C7AC 252E72C2 02FDCE0D");
+    let i = hex!("5BE3D612 17B96181 FE6786AD 716B890B");
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let y = hex!(
+        "49A38EE1 08D6C742 E52B774F 00A6EF98 B106CBD1 3EA4FB06 80323051 BC04DF76"
+        "E487B055 C69BCF54 1176169F 1DC9F6C8"
+    );
+
+    let kw = BeltKwp::new(&k.into());
+    let mut blob = [0u8; 64];
+    let res = kw.wrap_key_combined(&x, &i, &mut blob).unwrap();
+    assert_eq!(res[..16], i);
+    assert_eq!(res[16..], y);
+
+    let mut buf = [0u8; 48];
+    let res = kw.unwrap_key_combined(&blob, &mut buf).unwrap();
+    assert_eq!(res, x);
+
+    blob[0] ^= 1;
+    let res = kw.unwrap_key_combined(&blob, &mut buf);
+    assert_eq!(res, Err(Error::IntegrityCheckFailed));
+
+    let res = kw.wrap_key_combined(&x, &i, &mut blob[..63]);
+    assert_eq!(res, Err(Error::InvalidOutputSize { expected_len: 64 }));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn belt_kwp_verify() {
+    let i = hex!("5BE3D612 17B96181 FE6786AD 716B890B");
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let y = hex!(
+        "49A38EE1 08D6C742 E52B774F 00A6EF98 B106CBD1 3EA4FB06 80323051 BC04DF76"
+        "E487B055 C69BCF54 1176169F 1DC9F6C8"
+    );
+
+    let kw = BeltKwp::new(&k.into());
+    assert_eq!(kw.verify(&y, &i), Ok(()));
+    assert_eq!(kw.verify(&y, &DEFAULT_IV), Err(Error::IntegrityCheckFailed));
+    assert_eq!(kw.verify(&y[..31], &i), Err(Error::InvalidDataSize));
+}
+
+#[test]
+fn belt_kwp_semiblocks() {
+    let y = hex!(
+        "49A38EE1 08D6C742 E52B774F 00A6EF98 B106CBD1 3EA4FB06 80323051 BC04DF76"
+        "E487B055 C69BCF54 1176169F 1DC9F6C8"
+    );
+
+    let mut iter = belt_kwp::semiblocks(&y).unwrap();
+    assert_eq!(
+        iter.next(),
+        Some(&hex!("49A38EE1 08D6C742 E52B774F 00A6EF98"))
+    );
+    assert_eq!(
+        iter.last(),
+        Some(&hex!("E487B055 C69BCF54 1176169F 1DC9F6C8"))
+    );
+    assert!(matches!(
+        belt_kwp::semiblocks(&y[..47]),
+        Err(Error::NotSemiblockAligned)
+    ));
+}
+
+#[test]
+fn belt_kwp_key_wrap_traits() {
+    use belt_kwp::KeyWrap;
+
+    let x = hex!("B194BAC8 0A08F53B 366D008E 584A5DE4 8504FA9D 1BB6C7AC 252E72C2 02FDCE0D");
+    let i = hex!("5BE3D612 17B96181 FE6786AD 716B890B");
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let y = hex!(
+        "49A38EE1 08D6C742 E52B774F 00A6EF98 B106CBD1 3EA4FB06 80323051 BC04DF76"
+        "E487B055 C69BCF54 1176169F 1DC9F6C8"
+    );
+
+    let kw: &dyn KeyWrap = &BeltKwp::new(&k.into());
+    let mut buf = [0u8; 48];
+    assert_eq!(kw.wrap_len(x.len()), Ok(48));
+    assert_eq!(kw.wrap_key_with_iv(&x, &i, &mut buf).unwrap(), y);
+    assert_eq!(kw.unwrap_key_with_iv(&y, &i, &mut buf).unwrap(), x);
+    assert_eq!(
+        kw.unwrap_key_default(&y, &mut buf),
+        Err(Error::IntegrityCheckFailed)
+    );
+    assert_eq!(
+        kw.wrap_key_with_iv(&x, &i[..8], &mut buf),
+        Err(Error::InvalidIvSize { expected_len: 16 })
+    );
+}
+
+#[test]
+fn belt_kwp_unwrap_key_len() {
+    let i = hex!("5BE3D612 17B96181 FE6786AD 716B890B");
+    let k = hex!("E9DEE72C 8F0C0FA6 2DDB49F4 6F739647 06075316 ED247A37 39CBA383 03A98BF6");
+    let y = hex!(
+        "49A38EE1 08D6C742 E52B774F 00A6EF98 B106CBD1 3EA4FB06 80323051 BC04DF76"
+        "E487B055 C69BCF54 1176169F 1DC9F6C8"
+    );
+
+    assert_eq!(BeltKwp::unwrap_key_len(y.len()), Ok(32));
+    assert_eq!(BeltKwp::unwrap_key_len(32), Ok(16));
+    assert_eq!(BeltKwp::unwrap_key_len(31), Err(Error::InvalidDataSize));
+
+    // Bytes of `out` beyond `y.len()` are not used
+    let kw = BeltKwp::new(&k.into());
+    let mut out = [0xFF; 64];
+    let res = kw.unwrap_key(&y, &i, &mut out).unwrap();
+    assert_eq!(res.len(), BeltKwp::unwrap_key_len(y.len()).unwrap());
+    assert_eq!(out[32..48], i);
+    assert_eq!(out[48..], [0xFF; 16]);
 }
@@ -0,0 +1,10 @@
+//! Compile-fail tests for the fixed-size key length bounds.
+//!
+//! The expected compiler output depends on the compiler version, so these
+//! tests are ignored by default and run only on the stable toolchain in CI.
+#[test]
+#[ignore = "compiler output depends on the toolchain version"]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,6 @@
+use belt_kwp::{cipher::consts::U8, BeltKwp, KeyInit, DEFAULT_IV};
+
+fn main() {
+    let kw = BeltKwp::new(&[0u8; 32].into());
+    let _ = kw.wrap_fixed_key::<U8>(&[0u8; 8].into(), &DEFAULT_IV);
+}
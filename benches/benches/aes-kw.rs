@@ -0,0 +1,80 @@
+use aes_kw::{KeyInit, KwAes128, KwAes256, KwpAes128};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+const KEY_LENS: &[usize] = &[16, 32, 64, 256, 4096];
+
+fn aes_kw(c: &mut Criterion) {
+    let mut group = c.benchmark_group("aes-kw");
+    let kw128 = KwAes128::new(&[0x42; 16].into());
+    let kw256 = KwAes256::new(&[0x42; 32].into());
+
+    for &len in KEY_LENS {
+        let key = vec![0x24; len];
+        let mut wkey = vec![0; len + KwAes128::OVERHEAD];
+        let mut buf = vec![0; len];
+        group.throughput(Throughput::Bytes(len as u64));
+
+        group.bench_with_input(BenchmarkId::new("wrap128", len), &key, |b, key| {
+            b.iter(|| kw128.wrap_key(key, &mut wkey).unwrap().len())
+        });
+        group.bench_with_input(BenchmarkId::new("wrap256", len), &key, |b, key| {
+            b.iter(|| kw256.wrap_key(key, &mut wkey).unwrap().len())
+        });
+
+        kw128.wrap_key(&key, &mut wkey).unwrap();
+        group.bench_with_input(BenchmarkId::new("unwrap128", len), &wkey, |b, wkey| {
+            b.iter(|| kw128.unwrap_key(wkey, &mut buf).unwrap().len())
+        });
+    }
+    group.finish();
+}
+
+fn aes_kwp(c: &mut Criterion) {
+    let mut group = c.benchmark_group("aes-kwp");
+    let kwp = KwpAes128::new(&[0x42; 16].into());
+
+    // Odd lengths exercise the padding path
+    for &len in KEY_LENS {
+        let len = len - 1;
+        let key = vec![0x24; len];
+        let mut wkey = vec![0; KwpAes128::required_wrap_len(len).unwrap()];
+        let mut buf = vec![0; wkey.len() - KwpAes128::OVERHEAD];
+        group.throughput(Throughput::Bytes(len as u64));
+
+        group.bench_with_input(BenchmarkId::new("wrap128", len), &key, |b, key| {
+            b.iter(|| kwp.wrap_key(key, &mut wkey).unwrap().len())
+        });
+
+        kwp.wrap_key(&key, &mut wkey).unwrap();
+        group.bench_with_input(BenchmarkId::new("unwrap128", len), &wkey, |b, wkey| {
+            b.iter(|| kwp.unwrap_key(wkey, &mut buf).unwrap().len())
+        });
+    }
+    group.finish();
+}
+
+fn aes_kw_batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("aes-kw-batch");
+    let kw = KwAes256::new(&[0x42; 32].into());
+    let keys = vec![[0x24u8; 32]; 4096];
+    group.throughput(Throughput::Bytes((keys.len() * 32) as u64));
+
+    group.bench_function("wrap256", |b| {
+        b.iter(|| {
+            keys.iter()
+                .map(|key| {
+                    let mut buf = vec![0; key.len() + KwAes256::OVERHEAD];
+                    kw.wrap_key(key, &mut buf).unwrap();
+                    buf
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("wrap256_par", |b| {
+        b.iter(|| kw.wrap_keys_par(&keys).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, aes_kw, aes_kwp, aes_kw_batch);
+criterion_main!(benches);
@@ -0,0 +1,35 @@
+//! Feed arbitrary KEKs and wrapped keys into the unwrap functions.
+//!
+//! Unwrapping must never panic, regardless of the input.
+#![no_main]
+
+use aes_kw::{KeyInit, KwAes128, KwpAes128};
+use belt_kwp::BeltKwp;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let mut buf = [0u8; 1024];
+
+    if let Some((kek, wkey)) = data.split_first_chunk::<16>() {
+        // Also exercise the output buffer size checks
+        let short_len = (wkey.len() / 2).min(buf.len());
+
+        let kw = KwAes128::new(kek.into());
+        let _ = kw.unwrap_key(wkey, &mut buf);
+        let _ = kw.unwrap_key(wkey, &mut buf[..short_len]);
+
+        let kwp = KwpAes128::new(kek.into());
+        let _ = kwp.unwrap_key(wkey, &mut buf);
+        let _ = kwp.unwrap_key(wkey, &mut buf[..short_len]);
+    }
+
+    if let Some((kek, rest)) = data.split_first_chunk::<32>() {
+        if let Some((iv, wkey)) = rest.split_first_chunk::<16>() {
+            let short_len = (wkey.len() / 2).min(buf.len());
+
+            let belt = BeltKwp::new(kek.into());
+            let _ = belt.unwrap_key(wkey, iv, &mut buf);
+            let _ = belt.unwrap_key(wkey, iv, &mut buf[..short_len]);
+        }
+    }
+});
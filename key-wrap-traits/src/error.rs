@@ -0,0 +1,133 @@
+use core::fmt;
+
+/// Errors emitted from the wrap and unwrap operations.
+///
+/// New variants may be added in minor releases, so `match` expressions
+/// on this enum outside of this crate must include a wildcard arm.
+#[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// Input data length invalid.
+    InvalidDataSize,
+
+    /// Data length is not a multiple of the semiblock size of the algorithm.
+    NotSemiblockAligned,
+
+    /// Wrapped data is shorter than the minimum supported length.
+    TooShort {
+        /// Minimum length in bytes.
+        min: usize,
+    },
+
+    /// Output buffer size invalid.
+    InvalidOutputSize {
+        /// Expected size in bytes.
+        expected_len: usize,
+    },
+
+    /// Integrity check did not pass.
+    IntegrityCheckFailed,
+
+    /// Integrity check did not pass, with the recovered integrity register.
+    ///
+    /// Returned only by debugging helpers which explicitly expose the recovered
+    /// integrity register. The integrity register is the result of decrypting
+    /// untrusted data with the KEK, so exposing it to an attacker may leak
+    /// information. The `debug-errors` feature must never be enabled
+    /// in production builds.
+    #[cfg(feature = "debug-errors")]
+    IntegrityCheckFailedDebug {
+        /// Recovered integrity register.
+        computed: [u8; 8],
+    },
+
+    /// IV length is not supported by the algorithm.
+    InvalidIvSize {
+        /// Expected size in bytes.
+        expected_len: usize,
+    },
+
+    /// Key-encryption key size is not supported by the cipher.
+    InvalidKekSize {
+        /// KEK size in bits.
+        bits: usize,
+    },
+
+    /// Wrapped key encoding is malformed.
+    InvalidEncoding,
+
+    /// Key wrap algorithm is not supported.
+    UnsupportedAlgorithm,
+
+    /// Length of the wrapped data can not be represented as `usize` on the target.
+    ///
+    /// Can be returned on targets with `usize` narrower than 64 bits.
+    LengthOverflow,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::InvalidDataSize => f.write_str("invalid data size"),
+            Error::NotSemiblockAligned => {
+                f.write_str("data length must be a multiple of the semiblock size")
+            }
+            Error::TooShort { min } => {
+                write!(
+                    f,
+                    "wrapped data is too short: expected at least {min} bytes"
+                )
+            }
+            Error::InvalidOutputSize { expected_len } => {
+                write!(f, "invalid output buffer size: expected {expected_len}")
+            }
+            Error::IntegrityCheckFailed => f.write_str("integrity check failed"),
+            #[cfg(feature = "debug-errors")]
+            Error::IntegrityCheckFailedDebug { computed } => {
+                f.write_str("integrity check failed: computed integrity register ")?;
+                computed.iter().try_for_each(|b| write!(f, "{b:02X}"))
+            }
+            Error::InvalidIvSize { expected_len } => {
+                write!(f, "invalid IV size: expected {expected_len}")
+            }
+            Error::InvalidKekSize { bits } => write!(f, "{bits}-bit KEK is not supported"),
+            Error::InvalidEncoding => f.write_str("invalid wrapped key encoding"),
+            Error::UnsupportedAlgorithm => f.write_str("unsupported key wrap algorithm"),
+            Error::LengthOverflow => f.write_str("wrapped data length overflows usize"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+#[cfg(feature = "std")]
+impl From<Error> for std::io::Error {
+    fn from(err: Error) -> Self {
+        let kind = match err {
+            Error::IntegrityCheckFailed | Error::InvalidEncoding => std::io::ErrorKind::InvalidData,
+            #[cfg(feature = "debug-errors")]
+            Error::IntegrityCheckFailedDebug { .. } => std::io::ErrorKind::InvalidData,
+            _ => std::io::ErrorKind::InvalidInput,
+        };
+        std::io::Error::new(kind, err)
+    }
+}
+
+/// Error that indicates integrity check failure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IntegrityCheckFailed;
+
+impl fmt::Display for IntegrityCheckFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("integrity check failed")
+    }
+}
+
+impl core::error::Error for IntegrityCheckFailed {}
+
+impl From<IntegrityCheckFailed> for Error {
+    #[inline]
+    fn from(_: IntegrityCheckFailed) -> Self {
+        Error::IntegrityCheckFailed
+    }
+}
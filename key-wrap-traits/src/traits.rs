@@ -0,0 +1,86 @@
+//! Object-safe key wrapping traits
+use crate::Error;
+
+/// Key wrapping algorithm which can be used as a trait object.
+///
+/// Unlike the inherent methods of the algorithm types (e.g. `AesKw` and `AesKwp`),
+/// methods of this trait do not depend on the algorithm-specific length
+/// constants or IV types, which allows storing different algorithms
+/// behind `dyn KeyWrapEncrypt`.
+///
+/// The IV interpretation is algorithm specific:
+///
+/// | Algorithm | IV length | IV meaning | Default IV |
+/// |-----------|-----------|------------|------------|
+/// | AES-KW    | 8         | integrity check value | `AES_KW_IV` |
+/// | AES-KWP   | 4         | AIV prefix | `KWP_AIV_PREFIX` |
+/// | BelT-KWP  | 16        | header     | all-zero `DEFAULT_IV` |
+pub trait KeyWrapEncrypt {
+    /// Length of IV accepted by [`KeyWrapEncrypt::wrap_key_with_iv`] in bytes.
+    fn iv_len(&self) -> usize;
+
+    /// Get length of the wrapped key for a key of length `key_len`.
+    ///
+    /// Returns [`Error::InvalidDataSize`] if `key_len` is not supported.
+    fn wrap_len(&self, key_len: usize) -> Result<usize, Error>;
+
+    /// Wrap `key` using the default IV of the algorithm and write result to `buf`.
+    ///
+    /// Returns slice which points to `buf` and contains wrapped data.
+    /// Length of `buf` must be bigger or equal to [`KeyWrapEncrypt::wrap_len`].
+    fn wrap_key_default<'a>(&self, key: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error>;
+
+    /// Wrap `key` using `iv` and write result to `buf`.
+    ///
+    /// Returns [`Error::InvalidIvSize`] if length of `iv` is not equal
+    /// to [`KeyWrapEncrypt::iv_len`]. Length requirements for `buf` are the same
+    /// as in [`KeyWrapEncrypt::wrap_key_default`].
+    fn wrap_key_with_iv<'a>(
+        &self,
+        key: &[u8],
+        iv: &[u8],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Error>;
+}
+
+/// Key unwrapping algorithm which can be used as a trait object.
+///
+/// See [`KeyWrapEncrypt`] for the algorithm-specific IV interpretation.
+pub trait KeyWrapDecrypt {
+    /// Length of IV accepted by [`KeyWrapDecrypt::unwrap_key_with_iv`] in bytes.
+    fn iv_len(&self) -> usize;
+
+    /// Get length of the buffer required for unwrapping a wrapped key of length `wkey_len`.
+    ///
+    /// The unwrapped key may be shorter than the returned value, e.g. because
+    /// of the padding or if the algorithm uses the buffer as a scratch space.
+    fn unwrap_len(&self, wkey_len: usize) -> Result<usize, Error>;
+
+    /// Unwrap `wkey` wrapped using the default IV of the algorithm and write
+    /// result to `buf`.
+    ///
+    /// Returns slice which points to `buf` and contains unwrapped key.
+    /// Length of `buf` must be bigger or equal to [`KeyWrapDecrypt::unwrap_len`].
+    /// On integrity check failure the used part of `buf` is zeroed.
+    fn unwrap_key_default<'a>(&self, wkey: &[u8], buf: &'a mut [u8]) -> Result<&'a [u8], Error>;
+
+    /// Unwrap `wkey` wrapped using `iv` and write result to `buf`.
+    ///
+    /// Returns [`Error::InvalidIvSize`] if length of `iv` is not equal
+    /// to [`KeyWrapDecrypt::iv_len`]. Length requirements for `buf` are the same
+    /// as in [`KeyWrapDecrypt::unwrap_key_default`].
+    fn unwrap_key_with_iv<'a>(
+        &self,
+        wkey: &[u8],
+        iv: &[u8],
+        buf: &'a mut [u8],
+    ) -> Result<&'a [u8], Error>;
+}
+
+/// Key wrapping algorithm supporting both wrapping and unwrapping.
+///
+/// This trait is implemented for all types which implement [`KeyWrapEncrypt`]
+/// and [`KeyWrapDecrypt`], e.g. it allows storing `Box<dyn KeyWrap>`.
+pub trait KeyWrap: KeyWrapEncrypt + KeyWrapDecrypt {}
+
+impl<T: KeyWrapEncrypt + KeyWrapDecrypt + ?Sized> KeyWrap for T {}